@@ -1,9 +1,9 @@
 use macroquad::color::Color;
 use uom::si::f64::{Acceleration, Length, Mass, Time, Velocity};
 
-use molecular_dynamics::physics::{potential::Potential, time_integration::StepType};
-use physics_core::vector::Vector2D;
-use visualization::simulation::config::SimulationConfig;
+use molecular_dynamics::physics::potential::Potential;
+use physics_core::{config::SimulationConfig, vector::Vector2D};
+use visualization::simulation::config::DisplayConfig;
 
 pub trait PhysicalObject {
     /// Method to reset acceleration before applying forces
@@ -16,7 +16,7 @@ pub trait PhysicalObject {
         config: &SimulationConfig,
     );
     /// Method to apply a time step to the object
-    fn step(&mut self, step_type: Option<&StepType>, time_step: Time);
+    fn step(&mut self, time_step: Time);
 
     // Getter methods for pairwise interactions
     fn pos(&self) -> Vector2D<Length>;
@@ -31,5 +31,5 @@ pub trait PhysicalObject {
     fn set_mass(&mut self, new_value: Mass);
 
     /// Method to draw the object to the Screen
-    fn draw(&self, config: &SimulationConfig, scale: Option<f32>, color: Color);
+    fn draw(&self, config: &DisplayConfig, scale: Option<f32>, color: Color);
 }
@@ -0,0 +1,270 @@
+//! Time correlation functions and Green-Kubo transport coefficients.
+//!
+//! Correlations are accumulated online: only the last `lags` frames are kept, and each
+//! new frame is correlated with all of them, averaging over time origins as the
+//! simulation runs. Frames are expected at a constant interval. In 2D the transport
+//! coefficients follow from
+//! D = ½ ∫ ⟨v(0)·v(t)⟩ dt
+//! η = A / (k_B × T) × ∫ ⟨Pₓᵧ(0) × Pₓᵧ(t)⟩ dt
+//! integrated up to the longest lag. Correlations in 2D decay slowly (as 1/t), so the
+//! integrals keep growing with the window and are best compared at a fixed one.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use uom::si::{
+    area::square_meter,
+    diffusion_coefficient::square_meter_per_second,
+    energy::joule,
+    f64::{Area, DiffusionCoefficient, Energy, Force, Length, Time},
+    force::newton,
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+
+use crate::{handler::SimulationHandler, observables::Observable};
+use physics_core::{
+    constants::thermal_energy,
+    quantities::{Pressure2D, Viscosity2D},
+};
+
+/// Running averages of ⟨a(0)·a(t)⟩ over time origins, for lags up to a fixed window
+#[derive(Debug, Clone)]
+struct Correlator {
+    /// Most recent samples, newest first
+    history: VecDeque<Vec<f64>>,
+    sums: Vec<f64>,
+    counts: Vec<u64>,
+    start: Option<Time>,
+    interval: Option<Time>,
+}
+
+impl Correlator {
+    fn new(lags: usize) -> Self {
+        let lags = lags.max(1);
+        Self {
+            history: VecDeque::with_capacity(lags),
+            sums: vec![0.; lags],
+            counts: vec![0; lags],
+            start: None,
+            interval: None,
+        }
+    }
+
+    fn frames(&self) -> u64 {
+        self.counts[0]
+    }
+
+    fn push(&mut self, time: Time, sample: Vec<f64>) {
+        match self.start {
+            None => self.start = Some(time),
+            Some(start) if self.interval.is_none() => self.interval = Some(time - start),
+            Some(_) => {}
+        }
+
+        if self.history.len() == self.sums.len() {
+            self.history.pop_back();
+        }
+        self.history.push_front(sample);
+
+        let current = &self.history[0];
+        for (lag, past) in self.history.iter().enumerate() {
+            self.sums[lag] += past.iter().zip(current).map(|(a, b)| a * b).sum::<f64>();
+            self.counts[lag] += 1;
+        }
+    }
+
+    /// Lag times and the averaged correlations, for lags sampled at least once
+    fn averages(&self) -> Vec<(Time, f64)> {
+        let interval = self.interval.unwrap_or_default();
+        self.sums
+            .iter()
+            .zip(&self.counts)
+            .take_while(|(_, count)| **count > 0)
+            .enumerate()
+            .map(|(lag, (sum, count))| {
+                #[allow(clippy::cast_precision_loss)]
+                let value = sum / *count as f64;
+                #[allow(clippy::cast_precision_loss)]
+                let lag = interval * lag as f64;
+                (lag, value)
+            })
+            .collect()
+    }
+
+    /// Trapezoidal integral of the correlation over all lags, in SI units times seconds
+    fn integral(&self) -> Option<f64> {
+        let averages = self.averages();
+        if averages.len() < 2 {
+            return None;
+        }
+        let interval = self.interval?.get::<second>();
+        let inner: f64 = averages.iter().map(|(_, value)| value).sum();
+        let ends = averages[0].1 + averages[averages.len() - 1].1;
+        Some(interval * (inner - ends / 2.))
+    }
+
+    /// Correlations divided by their value at zero lag
+    fn normalized(&self) -> Vec<(Time, f64)> {
+        let averages = self.averages();
+        let zero = averages.first().map_or(0., |(_, value)| *value);
+        averages
+            .into_iter()
+            .map(|(lag, value)| (lag, if zero > 0. { value / zero } else { 0. }))
+            .collect()
+    }
+
+    fn write_csv(&self, mut writer: impl Write, name: &str) -> io::Result<()> {
+        writeln!(writer, "t [s],{name}")?;
+        for (lag, value) in self.normalized() {
+            writeln!(writer, "{},{}", lag.get::<second>(), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Velocity autocorrelation function, averaged over particles: ⟨v(0)·v(t)⟩
+#[derive(Debug, Clone)]
+pub struct VelocityAutocorrelation {
+    correlator: Correlator,
+    particles: usize,
+}
+
+impl VelocityAutocorrelation {
+    /// Correlate frames up to `lags` frames apart
+    #[must_use]
+    pub fn new(lags: usize) -> Self {
+        Self {
+            correlator: Correlator::new(lags),
+            particles: 0,
+        }
+    }
+
+    /// Number of frames accumulated
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.correlator.frames()
+    }
+
+    /// Correlate the velocities of the current frame with those of earlier frames
+    pub fn accumulate(&mut self, handler: &SimulationHandler) {
+        let velocities = handler.velocities();
+        self.particles = velocities.len();
+        let sample = velocities
+            .iter()
+            .flat_map(|vel| {
+                [
+                    vel.x.get::<meter_per_second>(),
+                    vel.y.get::<meter_per_second>(),
+                ]
+            })
+            .collect();
+        self.correlator.push(handler.time(), sample);
+    }
+
+    /// Lag times and the VACF normalized to one at zero lag
+    #[must_use]
+    pub fn values(&self) -> Vec<(Time, f64)> {
+        self.correlator.normalized()
+    }
+
+    /// Self-diffusion coefficient D = ½ ∫ ⟨v(0)·v(t)⟩ dt.
+    /// Returns `None` until at least two lags have been sampled.
+    #[must_use]
+    pub fn diffusion_coefficient(&self) -> Option<DiffusionCoefficient> {
+        #[allow(clippy::cast_precision_loss)]
+        let particles = self.particles.max(1) as f64;
+        let integral = self.correlator.integral()? / particles;
+        Some(DiffusionCoefficient::new::<square_meter_per_second>(
+            integral / 2.,
+        ))
+    }
+
+    /// Write the normalized VACF as CSV with columns `t` (in seconds) and `vacf`
+    ///
+    /// # Errors
+    /// Returns any error from writing to `writer`.
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        self.correlator.write_csv(writer, "vacf")
+    }
+}
+
+impl Observable for VelocityAutocorrelation {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        self.accumulate(handler);
+    }
+}
+
+/// Shear stress autocorrelation ⟨Pₓᵧ(0) × Pₓᵧ(t)⟩ and the shear viscosity.
+/// Only frames with a periodic simulation box contribute.
+#[derive(Debug, Clone)]
+pub struct ShearViscosity {
+    correlator: Correlator,
+    /// Σ A / k_B × T over all frames, in m²/J
+    area_per_energy_sum: f64,
+}
+
+impl ShearViscosity {
+    /// Correlate frames up to `lags` frames apart
+    #[must_use]
+    pub fn new(lags: usize) -> Self {
+        Self {
+            correlator: Correlator::new(lags),
+            area_per_energy_sum: 0.,
+        }
+    }
+
+    /// Number of frames accumulated
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.correlator.frames()
+    }
+
+    /// Correlate the shear stress of the current frame with that of earlier frames
+    pub fn accumulate(&mut self, handler: &SimulationHandler) {
+        let (Some(simulation_box), Some(pressure_xy)) =
+            (handler.simulation_box(), handler.pressure_xy())
+        else {
+            return;
+        };
+        let thermal: Energy = thermal_energy(handler.temperature());
+        self.area_per_energy_sum += simulation_box.area().value / thermal.value;
+        self.correlator
+            .push(handler.time(), vec![pressure_xy.value]);
+    }
+
+    /// Lag times and the stress autocorrelation normalized to one at zero lag
+    #[must_use]
+    pub fn values(&self) -> Vec<(Time, f64)> {
+        self.correlator.normalized()
+    }
+
+    /// Shear viscosity η = A / (k_B × T) × ∫ ⟨Pₓᵧ(0) × Pₓᵧ(t)⟩ dt, averaging A / k_B × T
+    /// over the frames. Returns `None` until at least two lags have been sampled.
+    #[must_use]
+    pub fn viscosity(&self) -> Option<Viscosity2D> {
+        let integral = self.correlator.integral()?;
+        #[allow(clippy::cast_precision_loss)]
+        let area_per_energy =
+            Area::new::<square_meter>(self.area_per_energy_sum / self.frames() as f64)
+                / Energy::new::<joule>(1.);
+        let unit_stress: Pressure2D = Force::new::<newton>(1.) / Length::new::<meter>(1.);
+        Some(area_per_energy * unit_stress * unit_stress * Time::new::<second>(integral))
+    }
+
+    /// Write the normalized stress autocorrelation as CSV with columns `t` (in seconds)
+    /// and `sacf`
+    ///
+    /// # Errors
+    /// Returns any error from writing to `writer`.
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        self.correlator.write_csv(writer, "sacf")
+    }
+}
+
+impl Observable for ShearViscosity {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        self.accumulate(handler);
+    }
+}
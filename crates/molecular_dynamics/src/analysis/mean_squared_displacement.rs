@@ -0,0 +1,192 @@
+//! Mean-squared displacement and the self-diffusion coefficient.
+//!
+//! The MSD is averaged over all particles and all time origins of the recorded
+//! frames, using the FFT-based algorithm of Kneller et al. (nMoldyn):
+//! MSD(m) = S₁(m) - 2·S₂(m), with S₂ the position autocorrelation computed by FFT
+//! and S₁ from a running sum of squared positions, in O(T log T) per particle.
+//! Frames are expected at a constant interval, e.g. as an `Observable`
+//! with a fixed time step. Unwrapped positions are used, so periodic boundaries
+//! do not cut off displacements.
+
+use std::io::{self, Write};
+
+use rustfft::{FftPlanner, num_complex::Complex};
+use uom::si::{
+    area::square_meter,
+    f64::{Area, DiffusionCoefficient, Length, Time},
+    length::meter,
+    time::second,
+};
+
+use crate::{handler::SimulationHandler, observables::Observable};
+use physics_core::{config::units::LengthUnit, vector::Vector2D};
+
+/// Recorded unwrapped positions, from which the MSD is computed
+#[derive(Debug, Clone, Default)]
+pub struct MeanSquaredDisplacement {
+    times: Vec<Time>,
+    frames: Vec<Vec<Vector2D<Length>>>,
+}
+
+impl MeanSquaredDisplacement {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of frames recorded
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Record the unwrapped positions of the current frame
+    pub fn accumulate(&mut self, handler: &SimulationHandler) {
+        self.record(handler.time(), handler.unwrapped_positions());
+    }
+
+    /// Record a frame of unwrapped positions taken at `time`, e.g. as read from a
+    /// trajectory. Frames are expected at a constant interval.
+    pub fn record(&mut self, time: Time, unwrapped_positions: Vec<Vector2D<Length>>) {
+        self.times.push(time);
+        self.frames.push(unwrapped_positions);
+    }
+
+    /// Lag times and the MSD at each, averaged over particles and time origins
+    #[must_use]
+    pub fn values(&self) -> Vec<(Time, Area)> {
+        let frame_count = self.frames.len();
+        let Some(particle_count) = self.frames.first().map(Vec::len) else {
+            return Vec::new();
+        };
+        let interval = if frame_count > 1 {
+            self.times[1] - self.times[0]
+        } else {
+            Time::default()
+        };
+
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(2 * frame_count);
+        let ifft = planner.plan_fft_inverse(2 * frame_count);
+
+        let mut msd = vec![0.; frame_count];
+        for particle in 0..particle_count {
+            // Displacements from the first frame, in meters, to limit cancellation
+            let origin = self.frames[0][particle];
+            let series: Vec<(f64, f64)> = self
+                .frames
+                .iter()
+                .map(|frame| {
+                    let d = frame[particle] - origin;
+                    (d.x.get::<meter>(), d.y.get::<meter>())
+                })
+                .collect();
+
+            let s2_x = autocorrelation(series.iter().map(|d| d.0), &*fft, &*ifft);
+            let s2_y = autocorrelation(series.iter().map(|d| d.1), &*fft, &*ifft);
+
+            // S₁ from the running sum Q of squared positions
+            let squares: Vec<f64> = series.iter().map(|(x, y)| x * x + y * y).collect();
+            let mut q = 2. * squares.iter().sum::<f64>();
+            for m in 0..frame_count {
+                if m > 0 {
+                    q -= squares[m - 1] + squares[frame_count - m];
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let s1 = q / (frame_count - m) as f64;
+                msd[m] += s1 - 2. * (s2_x[m] + s2_y[m]);
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let particle_count = particle_count as f64;
+        msd.iter()
+            .enumerate()
+            .map(|(m, value)| {
+                #[allow(clippy::cast_precision_loss)]
+                let lag = interval * m as f64;
+                (lag, Area::new::<square_meter>(value / particle_count))
+            })
+            .collect()
+    }
+
+    /// Self-diffusion coefficient from a least-squares fit of the Einstein relation
+    /// in 2D, MSD(t) = 4·D·t + c, over lag times between `start` and `end`.
+    /// Returns `None` if fewer than two lag times fall in that range.
+    #[must_use]
+    pub fn diffusion_coefficient(&self, start: Time, end: Time) -> Option<DiffusionCoefficient> {
+        let points: Vec<(f64, f64)> = self
+            .values()
+            .into_iter()
+            .filter(|(lag, _)| *lag >= start && *lag <= end)
+            .map(|(lag, msd)| (lag.get::<second>(), msd.get::<square_meter>()))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = points.len() as f64;
+        let mean_t = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_msd = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let covariance: f64 = points
+            .iter()
+            .map(|(t, msd)| (t - mean_t) * (msd - mean_msd))
+            .sum();
+        let variance: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+        let slope = Area::new::<square_meter>(covariance / variance) / Time::new::<second>(1.);
+
+        Some(slope / 4.)
+    }
+
+    /// Write the MSD as CSV with columns `t` (in seconds) and `msd` (in `unit`²)
+    ///
+    /// # Errors
+    /// Returns any error from writing to `writer`.
+    pub fn write_csv(&self, mut writer: impl Write, unit: LengthUnit) -> io::Result<()> {
+        let unit_per_meter = unit.get(Length::new::<meter>(1.));
+        writeln!(writer, "t [s],msd [{unit:?}^2]")?;
+        for (lag, msd) in self.values() {
+            let msd = msd.get::<square_meter>() * unit_per_meter * unit_per_meter;
+            writeln!(writer, "{},{}", lag.get::<second>(), msd)?;
+        }
+        Ok(())
+    }
+}
+
+impl Observable for MeanSquaredDisplacement {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        self.accumulate(handler);
+    }
+}
+
+/// Autocorrelation averaged over time origins, Σₖ x(k)·x(k + m) / (T - m),
+/// computed by FFT of the zero-padded series
+fn autocorrelation(
+    series: impl ExactSizeIterator<Item = f64>,
+    fft: &dyn rustfft::Fft<f64>,
+    ifft: &dyn rustfft::Fft<f64>,
+) -> Vec<f64> {
+    let len = series.len();
+    let mut buffer: Vec<Complex<f64>> = series
+        .map(|x| Complex::new(x, 0.))
+        .chain(std::iter::repeat_n(Complex::new(0., 0.), len))
+        .collect();
+
+    fft.process(&mut buffer);
+    for value in &mut buffer {
+        *value = Complex::new(value.norm_sqr(), 0.);
+    }
+    ifft.process(&mut buffer);
+
+    // rustfft does not normalize, so the round trip scales by the buffer length
+    #[allow(clippy::cast_precision_loss)]
+    let scale = (2 * len) as f64;
+    (0..len)
+        .map(|m| {
+            #[allow(clippy::cast_precision_loss)]
+            let origins = (len - m) as f64;
+            buffer[m].re / scale / origins
+        })
+        .collect()
+}
@@ -0,0 +1,13 @@
+//! Structural and dynamical analysis of simulation frames.
+//!
+//! Analyzers accumulate statistics from a `SimulationHandler` frame by frame, so they
+//! can run alongside a simulation without storing the trajectory. Each one also
+//! implements `Observable`, to be sampled by the handler at a fixed interval.
+
+pub mod green_kubo;
+pub mod mean_squared_displacement;
+pub mod radial_distribution;
+
+pub use green_kubo::{ShearViscosity, VelocityAutocorrelation};
+pub use mean_squared_displacement::MeanSquaredDisplacement;
+pub use radial_distribution::RadialDistribution;
@@ -0,0 +1,135 @@
+//! Radial distribution function g(r).
+//!
+//! Pair distances are binned into a histogram, which is normalized by the number of
+//! pairs an ideal gas of the same density would have in each annulus:
+//! g(r) = ⟨n(r)⟩ / (N(N - 1)/2A × π((r + Δr)² - r²))
+//! Distances use the minimum image, so only frames with a periodic box contribute
+//! and `max_radius` should not exceed half the box width.
+
+use std::f64::consts::PI;
+use std::io::{self, Write};
+
+use uom::si::{
+    f64::{Area, Length},
+    ratio::ratio,
+};
+
+use crate::{
+    handler::SimulationHandler,
+    observables::Observable,
+    physics::simulation_box::{SimulationBox, separation},
+};
+use physics_core::{config::units::LengthUnit, vector::Vector2D};
+
+/// Histogram of pair distances, accumulated over frames
+#[derive(Debug, Clone)]
+pub struct RadialDistribution {
+    max_radius: Length,
+    counts: Vec<u64>,
+    /// Σ N(N - 1)/2A over all frames, the ideal pair density in m⁻²
+    pair_density_sum: f64,
+    frames: usize,
+}
+
+impl RadialDistribution {
+    /// Initialise an empty histogram of `bins` bins between zero and `max_radius`
+    #[must_use]
+    pub fn new(max_radius: Length, bins: usize) -> Self {
+        Self {
+            max_radius,
+            counts: vec![0; bins.max(1)],
+            pair_density_sum: 0.,
+            frames: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn bin_width(&self) -> Length {
+        #[allow(clippy::cast_precision_loss)]
+        let bins = self.counts.len() as f64;
+        self.max_radius / bins
+    }
+
+    /// Number of frames accumulated
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Add the pair distances of the current frame.
+    /// Frames without a periodic simulation box are skipped.
+    pub fn accumulate(&mut self, handler: &SimulationHandler) {
+        if let Some(simulation_box) = handler.simulation_box() {
+            self.accumulate_positions(handler.positions(), simulation_box);
+        }
+    }
+
+    /// Add the pair distances of a frame given by its positions in a periodic box,
+    /// e.g. as read from a trajectory
+    pub fn accumulate_positions(
+        &mut self,
+        positions: &[Vector2D<Length>],
+        simulation_box: &SimulationBox,
+    ) {
+        let bin_width = self.bin_width();
+        let last_bin = self.counts.len() - 1;
+
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                let distance = separation(positions[i], positions[j], Some(simulation_box)).mag();
+                if distance < self.max_radius {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let bin = (distance / bin_width).get::<ratio>().floor() as usize;
+                    self.counts[bin.min(last_bin)] += 1;
+                }
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = positions.len() as f64;
+        let area: Area = simulation_box.area();
+        self.pair_density_sum += n * (n - 1.) / 2. / area.value;
+        self.frames += 1;
+    }
+
+    /// Centers of the bins and the corresponding values of g(r)
+    #[must_use]
+    pub fn values(&self) -> Vec<(Length, f64)> {
+        let bin_width = self.bin_width();
+        (0..self.counts.len())
+            .map(|bin| {
+                #[allow(clippy::cast_precision_loss)]
+                let inner = bin_width * bin as f64;
+                let outer = inner + bin_width;
+                let annulus: Area = PI * (outer * outer - inner * inner);
+
+                #[allow(clippy::cast_precision_loss)]
+                let count = self.counts[bin] as f64;
+                let g = if self.pair_density_sum > 0. {
+                    count / (self.pair_density_sum * annulus.value)
+                } else {
+                    0.
+                };
+                (inner + bin_width / 2., g)
+            })
+            .collect()
+    }
+
+    /// Write the bins as CSV with columns `r` (in `unit`) and `g`
+    ///
+    /// # Errors
+    /// Returns any error from writing to `writer`.
+    pub fn write_csv(&self, mut writer: impl Write, unit: LengthUnit) -> io::Result<()> {
+        writeln!(writer, "r [{unit:?}],g")?;
+        for (r, g) in self.values() {
+            writeln!(writer, "{},{}", unit.get(r), g)?;
+        }
+        Ok(())
+    }
+}
+
+impl Observable for RadialDistribution {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        self.accumulate(handler);
+    }
+}
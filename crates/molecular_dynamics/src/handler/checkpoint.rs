@@ -0,0 +1,176 @@
+//! Checkpoints holding the complete state of a simulation.
+//!
+//! A checkpoint starts with the magic bytes "MDCHKPT\0" and a little-endian u32 format
+//! version, followed by the state encoded with `bincode`. Together with the particle
+//! arrays it stores the box, step count, time, random number generator, the name and
+//! internal state of the integrator, the state of the thermostat and barostat, the
+//! neighbor list and the configuration, so a restored run continues bit-for-bit
+//! identically.
+//!
+//! Not stored are the observables, which need to be added again, the recorded step
+//! energies and reports, and the friction coefficient of the `Langevin` integrator,
+//! which is taken from the integrator built by `load_checkpoint`. Only its target
+//! temperature and random number generator are restored.
+
+use std::io::{self, Read, Write};
+
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use uom::si::f64::Time;
+
+use crate::{
+    handler::{SimulationHandler, forces::PairSums},
+    physics::{
+        barostat::Barostat,
+        neighbor_list::NeighborList,
+        simulation_box::{Image, SimulationBox},
+        thermostat::Thermostat,
+        time_integration::{Integrator, ParticleState},
+    },
+    point_mass::PointMass,
+};
+use physics_core::config::SimulationConfig;
+
+const MAGIC: &[u8; 8] = b"MDCHKPT\0";
+const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    config: SimulationConfig,
+    state: ParticleState,
+    integrator: String,
+    integrator_state: Vec<u8>,
+    neighbor_list: Option<NeighborList>,
+    simulation_box: Option<SimulationBox>,
+    images: Vec<Image>,
+    thermostat: Option<Thermostat>,
+    barostat: Option<Barostat>,
+    pair_sums: PairSums,
+    rng: Option<ChaCha8Rng>,
+    accelerations_current: bool,
+    time: Time,
+    step_count: u64,
+}
+
+impl SimulationHandler {
+    /// Write the complete state of the simulation, and the `config` it runs with,
+    /// as a checkpoint. Observables are not stored.
+    ///
+    /// # Errors
+    /// Returns any error from writing, or from encoding the integrator state.
+    pub fn save_checkpoint(
+        &self,
+        mut writer: impl Write,
+        config: &SimulationConfig,
+    ) -> io::Result<()> {
+        let checkpoint = Checkpoint {
+            config: config.clone(),
+            state: self.state.clone(),
+            integrator: self.integrator.name().to_string(),
+            integrator_state: self.integrator.save_state()?,
+            neighbor_list: self.neighbor_list.clone(),
+            simulation_box: self.simulation_box,
+            images: self.images.clone(),
+            thermostat: self.thermostat.clone(),
+            barostat: self.barostat.clone(),
+            pair_sums: self.pair_sums,
+            rng: self.rng.clone(),
+            accelerations_current: self.accelerations_current,
+            time: self.time,
+            step_count: self.step_count,
+        };
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, &checkpoint).map_err(|err| into_io_error(*err))?;
+        writer.flush()
+    }
+
+    /// Restore a simulation from a checkpoint, returning the handler and the
+    /// configuration it ran with. `build_integrator` is given that configuration and
+    /// must return an integrator of the same type as the one in use when the
+    /// checkpoint was saved, which receives its internal state.
+    /// Observables need to be added again.
+    ///
+    /// # Errors
+    /// Returns any error from reading, or an error of kind `InvalidData` if the
+    /// checkpoint is of an unsupported version, was written by a different integrator
+    /// or cannot be decoded.
+    pub fn load_checkpoint(
+        mut reader: impl Read,
+        build_integrator: impl FnOnce(&SimulationConfig) -> Box<dyn Integrator>,
+    ) -> io::Result<(Self, SimulationConfig)> {
+        let mut header = [0u8; 12];
+        reader.read_exact(&mut header)?;
+        if &header[0..8] != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a simulation checkpoint",
+            ));
+        }
+        let version = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported checkpoint version {version}"),
+            ));
+        }
+
+        let checkpoint: Checkpoint =
+            bincode::deserialize_from(&mut reader).map_err(|err| into_io_error(*err))?;
+        let mut integrator = build_integrator(&checkpoint.config);
+        if checkpoint.integrator != integrator.name() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "checkpoint was written by the {} integrator, but is resumed with {}",
+                    checkpoint.integrator,
+                    integrator.name()
+                ),
+            ));
+        }
+        integrator.load_state(&checkpoint.integrator_state)?;
+
+        let state = checkpoint.state;
+        let points = (0..state.len())
+            .map(|i| {
+                Box::new(
+                    PointMass::new(
+                        state.positions[i],
+                        state.velocities[i],
+                        state.accelerations[i],
+                        state.masses[i],
+                    )
+                    .with_species(state.species[i]),
+                )
+            })
+            .collect();
+
+        let handler = Self {
+            points,
+            state,
+            integrator,
+            neighbor_list: checkpoint.neighbor_list,
+            simulation_box: checkpoint.simulation_box,
+            images: checkpoint.images,
+            thermostat: checkpoint.thermostat,
+            barostat: checkpoint.barostat,
+            pair_sums: checkpoint.pair_sums,
+            step_energies: Vec::new(),
+            observables: Vec::new(),
+            rng: checkpoint.rng,
+            accelerations_current: checkpoint.accelerations_current,
+            time: checkpoint.time,
+            step_count: checkpoint.step_count,
+            step_reports: Vec::new(),
+        };
+        Ok((handler, checkpoint.config))
+    }
+}
+
+fn into_io_error(err: bincode::ErrorKind) -> io::Error {
+    match err {
+        bincode::ErrorKind::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
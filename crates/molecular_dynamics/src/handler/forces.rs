@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use uom::si::f64::{Acceleration, Energy, Length};
+
+use crate::physics::{
+    neighbor_list::NeighborList,
+    potential::Potential,
+    simulation_box::{SimulationBox, separation},
+    time_integration::{ForceField, ParticleState},
+};
+use physics_core::{config::SimulationConfig, vector::Vector2D};
+
+/// Neighbor list skin, relative to the cutoff, if the config sets none: 0.3σ for the
+/// common Lennard-Jones cutoff of 2.5σ
+const DEFAULT_SKIN_PER_CUTOFF: f64 = 0.12;
+
+/// Sums over all pairs, accumulated alongside the forces
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub(crate) struct PairSums {
+    pub potential_energy: Energy,
+    /// Virial Σ rᵢⱼ·Fᵢⱼ
+    pub virial: Energy,
+    /// Off-diagonal virial Σ ½(xᵢⱼ × Fᵢⱼ,ᵧ + yᵢⱼ × Fᵢⱼ,ₓ)
+    pub virial_xy: Energy,
+}
+
+/// Pair-wise forces from a potential, using a neighbor list if it has a cutoff
+pub(crate) struct PairForces<'a, P: Potential> {
+    pub potential: &'a P,
+    pub config: &'a SimulationConfig,
+    pub simulation_box: Option<&'a SimulationBox>,
+    pub neighbor_list: &'a mut Option<NeighborList>,
+    /// Sums over all pairs from the last force computation
+    pub sums: &'a mut PairSums,
+}
+
+impl<P: Potential> PairForces<'_, P> {
+    /// Call `f` for every pair `(i, j)` with `i < j` that may interact
+    fn for_each_pair(&mut self, positions: &[Vector2D<Length>], mut f: impl FnMut(usize, usize)) {
+        let Some(cutoff) = self.potential.cutoff() else {
+            for i in 0..positions.len() {
+                for j in (i + 1)..positions.len() {
+                    f(i, j);
+                }
+            }
+            return;
+        };
+
+        // (Re)create the list if the potential or config changed since the last step
+        let skin = self
+            .config
+            .neighbor_list_skin
+            .unwrap_or(DEFAULT_SKIN_PER_CUTOFF * cutoff);
+        let mut neighbor_list = match self.neighbor_list.take() {
+            Some(list) if list.cutoff() == cutoff && list.skin() == skin => list,
+            _ => NeighborList::new(cutoff, skin),
+        };
+        neighbor_list.update(positions, self.simulation_box);
+
+        for &(i, j) in neighbor_list.pairs() {
+            f(i, j);
+        }
+
+        *self.neighbor_list = Some(neighbor_list);
+    }
+
+    /// Total potential energy of all pairs
+    pub fn potential_energy(&mut self, state: &ParticleState) -> Energy {
+        let (potential, simulation_box) = (self.potential, self.simulation_box);
+        let mut energy = Energy::default();
+        self.for_each_pair(&state.positions, |i, j| {
+            energy += potential.energy_from_arrays(
+                i,
+                j,
+                &state.positions,
+                &state.masses,
+                &state.species,
+                simulation_box,
+            );
+        });
+        energy
+    }
+}
+
+impl<P: Potential> ForceField for PairForces<'_, P> {
+    fn compute_accelerations(&mut self, state: &mut ParticleState) {
+        for acc in &mut state.accelerations {
+            *acc = Vector2D::<Acceleration>::zero();
+        }
+
+        let (potential, config, simulation_box) =
+            (self.potential, self.config, self.simulation_box);
+        let mut sums = PairSums::default();
+        let ParticleState {
+            positions,
+            velocities,
+            accelerations,
+            masses,
+            species,
+        } = state;
+        self.for_each_pair(positions, |i, j| {
+            let force = potential.force_from_arrays(
+                i,
+                j,
+                positions,
+                velocities,
+                accelerations,
+                masses,
+                species,
+                simulation_box,
+                config,
+            );
+            accelerations[i] += force / masses[i];
+            accelerations[j] -= force / masses[j];
+
+            let r = separation(positions[i], positions[j], simulation_box);
+            sums.virial += r.x * force.x + r.y * force.y;
+            sums.virial_xy += 0.5 * (r.x * force.y + r.y * force.x);
+            sums.potential_energy +=
+                potential.energy_from_arrays(i, j, positions, masses, species, simulation_box);
+        });
+        *self.sums = sums;
+    }
+}
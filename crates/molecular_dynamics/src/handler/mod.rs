@@ -1,38 +1,114 @@
-use physics_core::vector::Vector2D;
-use uom::si::{
-    f64::{Acceleration, Length, Mass, Ratio, Time, Velocity},
-    ratio::ratio,
-};
-use visualization::simulation::config::SimulationConfig;
+mod checkpoint;
+mod forces;
+
+use std::{any::Any, io};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uom::si::f64::{Energy, Length, Mass, ThermodynamicTemperature, Time, Velocity};
 
 use crate::{
-    physics::{potential::Potential, time_integration::StepType},
-    point_mass::PointMass,
+    handler::forces::{PairForces, PairSums},
+    observables::Observable,
+    physics::{
+        barostat::{Barostat, virial_pressure},
+        neighbor_list::NeighborList,
+        potential::Potential,
+        simulation_box::{Image, SimulationBox},
+        thermostat::Thermostat,
+        time_integration::{ForceField, Integrator, ParticleState, StepReport, VelocityVerlet},
+    },
+    point_mass::{PointMass, Species, cluster::center_of_mass},
 };
+use physics_core::{config::SimulationConfig, quantities::Pressure2D, vector::Vector2D};
+
+/// Energy bookkeeping of a single step
+#[derive(Debug, Clone, Copy)]
+pub struct Energies {
+    /// Number of steps taken
+    pub step: u64,
+    /// Simulated time elapsed
+    pub time: Time,
+    pub kinetic: Energy,
+    pub potential: Energy,
+}
+
+impl Energies {
+    /// Total energy, conserved in the absence of thermostats and barostats
+    #[must_use]
+    pub fn total(&self) -> Energy {
+        self.kinetic + self.potential
+    }
+}
+
+/// An observable measured every `interval` steps
+struct ScheduledObservable {
+    interval: u64,
+    observable: Box<dyn Observable>,
+}
 
 pub struct SimulationHandler {
     pub points: Vec<Box<PointMass>>,
 
-    positions: Vec<Vector2D<Length>>,
-    velocities: Vec<Vector2D<Velocity>>,
-    accelerations: Vec<Vector2D<Acceleration>>,
-    masses: Vec<Mass>,
+    state: ParticleState,
+
+    integrator: Box<dyn Integrator>,
+
+    neighbor_list: Option<NeighborList>,
+
+    simulation_box: Option<SimulationBox>,
+    /// Number of times each particle was wrapped around the periodic box
+    images: Vec<Image>,
+
+    /// Built from the configuration on the first step, and rebuilt when it changes
+    thermostat: Option<Thermostat>,
+
+    /// Built from the configuration on the first step, and rebuilt when it changes
+    barostat: Option<Barostat>,
+
+    /// Potential energy and virial of the current accelerations
+    pair_sums: PairSums,
+    /// Energies after each step taken during the last call to `step_physics`
+    step_energies: Vec<Energies>,
+
+    /// Observables and the number of steps between their measurements
+    observables: Vec<ScheduledObservable>,
+
+    /// Random number generator of stochastic methods, seeded on the first step
+    rng: Option<ChaCha8Rng>,
+
+    /// Whether `accelerations` correspond to the current `positions`
+    accelerations_current: bool,
 
-    last_positions: Vec<Vector2D<Length>>,
-    last_velocities: Vec<Vector2D<Velocity>>,
+    /// Simulated time elapsed
+    time: Time,
+    /// Number of integrator steps taken
+    step_count: u64,
+    /// Reports of the steps taken during the last call to `step_physics`
+    step_reports: Vec<StepReport>,
 }
 
 impl SimulationHandler {
+    /// Initialise a handler for the given points, integrated using Velocity Verlet
     pub fn new(points: Vec<Box<PointMass>>) -> Self {
         let len = points.len();
         let mut handler = Self {
             points,
-            positions: vec![Vector2D::<Length>::zero(); len],
-            velocities: vec![Vector2D::<Velocity>::zero(); len],
-            accelerations: vec![Vector2D::<Acceleration>::zero(); len],
-            masses: vec![Mass::default(); len],
-            last_positions: vec![Vector2D::<Length>::zero(); len],
-            last_velocities: vec![Vector2D::<Velocity>::zero(); len],
+            state: ParticleState::zeros(len),
+            integrator: Box::new(VelocityVerlet),
+            neighbor_list: None,
+            simulation_box: None,
+            images: vec![(0, 0); len],
+            thermostat: None,
+            barostat: None,
+            pair_sums: PairSums::default(),
+            step_energies: Vec::new(),
+            observables: Vec::new(),
+            rng: None,
+            accelerations_current: false,
+            time: Time::default(),
+            step_count: 0,
+            step_reports: Vec::new(),
         };
 
         handler.sync_from_points();
@@ -41,94 +117,349 @@ impl SimulationHandler {
 
     pub fn sync_from_points(&mut self) {
         for (i, point) in self.points.iter().enumerate() {
-            self.positions[i] = point.pos();
-            self.velocities[i] = point.vel();
-            self.accelerations[i] = point.acc();
-            self.masses[i] = point.mass();
+            self.state.positions[i] = point.pos();
+            self.state.velocities[i] = point.vel();
+            self.state.accelerations[i] = point.acc();
+            self.state.masses[i] = point.mass();
+            self.state.species[i] = point.species();
         }
+        self.accelerations_current = false;
+        self.integrator.reset();
     }
 
     pub fn sync_to_points(&mut self) {
         for (i, point) in self.points.iter_mut().enumerate() {
-            point.set_pos(self.positions[i]);
-            point.set_vel(self.velocities[i]);
-            point.set_acc(self.accelerations[i]);
-            point.set_mass(self.masses[i]);
+            point.set_pos(self.state.positions[i]);
+            point.set_vel(self.state.velocities[i]);
+            point.set_acc(self.state.accelerations[i]);
+            point.set_mass(self.state.masses[i]);
+            point.set_species(self.state.species[i]);
         }
     }
 
+    /// Take `config.time_steps_per_frame` integrator steps.
+    /// For adaptive integrators, `time_step` is only the initial guess of the step size.
+    /// A configured thermostat is applied for half of the step taken before and after it,
+    /// a configured barostat after each step, before its energies and observables are recorded.
+    ///
+    /// # Errors
+    /// Returns an error if the integrator could not take a step, leaving the state as it
+    /// was after the last successful step.
     pub fn step_physics(
         &mut self,
         config: &SimulationConfig,
         potential: &impl Potential,
         time_step: Time,
-        movement_step_type: StepType,
-    ) {
+    ) -> io::Result<()> {
+        if self.thermostat.as_ref().map(Thermostat::config) != config.thermostat.as_ref() {
+            self.thermostat = config.thermostat.map(Thermostat::new);
+        }
+        if self.barostat.as_ref().map(Barostat::config) != config.barostat.as_ref() {
+            self.barostat = config.barostat.map(Barostat::new);
+        }
+        // Taken out for the duration of the call, so observables can borrow the handler
+        let mut rng = self.rng.take().unwrap_or_else(|| match config.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_os_rng(),
+        });
+        let mut observables = std::mem::take(&mut self.observables);
+
+        self.step_reports.clear();
+        self.step_energies.clear();
         for _ in 0..config.time_steps_per_frame.unwrap_or(1) {
-            for acc in &mut self.accelerations {
-                *acc = Vector2D::<Acceleration>::zero();
+            let mut forces = PairForces {
+                potential,
+                config,
+                simulation_box: self.simulation_box.as_ref(),
+                neighbor_list: &mut self.neighbor_list,
+                sums: &mut self.pair_sums,
+            };
+
+            // Forces from the previous step can be reused, unless state changed in between
+            if !self.accelerations_current {
+                forces.compute_accelerations(&mut self.state);
+                self.accelerations_current = true;
+            }
+
+            // Both halves cover the step the integrator takes, which for adaptive ones
+            // differs from `time_step`. Only after a rejected trial step does the first
+            // half still cover the trial step.
+            if let Some(thermostat) = &mut self.thermostat {
+                let planned_time_step = self.integrator.planned_time_step(time_step);
+                thermostat.apply(&mut self.state, planned_time_step / 2., &mut rng);
+            }
+
+            let report = match self
+                .integrator
+                .step(&mut self.state, &mut forces, time_step)
+            {
+                Ok(report) => report,
+                Err(err) => {
+                    self.rng = Some(rng);
+                    self.observables = observables;
+                    return Err(err);
+                }
+            };
+
+            if let Some(thermostat) = &mut self.thermostat {
+                thermostat.apply(&mut self.state, report.time_step / 2., &mut rng);
+            }
+
+            if let Some(simulation_box) = &mut self.simulation_box {
+                wrap_positions(simulation_box, &mut self.state, &mut self.images);
             }
 
-            for i in 0..self.accelerations.len() {
-                for j in (i + 1)..self.accelerations.len() {
-                    let force = potential.force_from_arrays(
-                        i,
-                        j,
-                        &self.positions,
-                        &self.velocities,
-                        &self.accelerations,
-                        &self.masses,
+            // Rescaling the box comes before recording, so that energies and observables
+            // describe the box the next step starts from
+            if let (Some(barostat), Some(simulation_box)) =
+                (&mut self.barostat, &mut self.simulation_box)
+            {
+                let pressure = virial_pressure(
+                    self.state.kinetic_energy(),
+                    self.pair_sums.virial,
+                    simulation_box.area(),
+                );
+                let neighbor_list = &mut self.neighbor_list;
+                let mut scratch_sums = PairSums::default();
+                let potential_energy = |state: &ParticleState, simulation_box: &SimulationBox| {
+                    PairForces {
+                        potential,
+                        config,
+                        simulation_box: Some(simulation_box),
+                        neighbor_list: &mut *neighbor_list,
+                        sums: &mut scratch_sums,
+                    }
+                    .potential_energy(state)
+                };
+
+                if barostat.apply(
+                    &mut self.state,
+                    simulation_box,
+                    pressure,
+                    report.time_step,
+                    potential_energy,
+                    &mut rng,
+                ) {
+                    PairForces {
+                        potential,
                         config,
-                    );
-                    self.accelerations[i] += force / self.masses[i];
-                    self.accelerations[j] -= force / self.masses[j];
+                        simulation_box: Some(simulation_box),
+                        neighbor_list: &mut self.neighbor_list,
+                        sums: &mut self.pair_sums,
+                    }
+                    .compute_accelerations(&mut self.state);
                 }
             }
 
-            for i in 0..self.positions.len() {
-                self.step_movement(i, time_step, &movement_step_type)
+            self.time += report.time_step;
+            self.step_count += 1;
+            self.step_reports.push(report);
+            self.step_energies.push(Energies {
+                step: self.step_count,
+                time: self.time,
+                kinetic: self.state.kinetic_energy(),
+                potential: self.pair_sums.potential_energy,
+            });
+
+            for scheduled in &mut observables {
+                if self.step_count.is_multiple_of(scheduled.interval) {
+                    scheduled.observable.observe(self);
+                }
             }
         }
+
+        self.rng = Some(rng);
+        self.observables = observables;
+        Ok(())
     }
 
-    fn step_movement(&mut self, idx: usize, time_step: Time, step_type: &StepType) {
-        match step_type {
-            StepType::Naive => self.naive_step(idx, time_step),
-            StepType::Verlet => self.verlet_step(idx, time_step),
-            StepType::VelocityVerlet => self.velocity_verlet_step(idx, time_step),
+    /// Kinetic and potential energy of the current state.
+    /// The potential energy is that of the last force evaluation, i.e. of the last step.
+    #[must_use]
+    pub fn energies(&self) -> Energies {
+        Energies {
+            step: self.step_count,
+            time: self.time,
+            kinetic: self.state.kinetic_energy(),
+            potential: self.pair_sums.potential_energy,
         }
     }
 
-    fn naive_step(&mut self, idx: usize, time_step: Time) {
-        self.positions[idx] += time_step * self.velocities[idx];
-        self.velocities[idx] += time_step * self.accelerations[idx];
+    /// Energies after each of the steps taken during the last `step_physics`
+    #[must_use]
+    pub fn step_energies(&self) -> &[Energies] {
+        &self.step_energies
     }
 
-    fn verlet_step(&mut self, idx: usize, time_step: Time) {
-        let prev_pos = self.last_positions[idx];
-        let current_pos = self.positions[idx];
+    /// Measure `observable` every `interval` steps, starting with the next step
+    pub fn add_observable(&mut self, observable: Box<dyn Observable>, interval: u64) {
+        self.observables.push(ScheduledObservable {
+            interval: interval.max(1),
+            observable,
+        });
+    }
 
-        self.positions[idx] = Ratio::new::<ratio>(2.) * self.positions[idx]
-            - self.last_positions[idx]
-            + time_step * (time_step * self.accelerations[idx]);
+    /// The first observable of type `T` that was added, e.g. to read back its samples
+    #[must_use]
+    pub fn observable<T: Observable>(&self) -> Option<&T> {
+        self.observables.iter().find_map(|scheduled| {
+            let observable: &dyn Any = scheduled.observable.as_ref();
+            observable.downcast_ref::<T>()
+        })
+    }
+
+    /// Instantaneous virial pressure, if the simulation box is periodic.
+    /// Long-range tail corrections of the potential are not included.
+    #[must_use]
+    pub fn pressure(&self) -> Option<Pressure2D> {
+        self.simulation_box.as_ref().map(|simulation_box| {
+            virial_pressure(
+                self.state.kinetic_energy(),
+                self.pair_sums.virial,
+                simulation_box.area(),
+            )
+        })
+    }
+
+    /// Off-diagonal element of the instantaneous pressure tensor, if the simulation box
+    /// is periodic: Pₓᵧ = (Σ m × vₓ × vᵧ + Σ ½(xᵢⱼ × Fᵢⱼ,ᵧ + yᵢⱼ × Fᵢⱼ,ₓ)) / A
+    #[must_use]
+    pub fn pressure_xy(&self) -> Option<Pressure2D> {
+        self.simulation_box.as_ref().map(|simulation_box| {
+            let kinetic = self
+                .state
+                .velocities
+                .iter()
+                .zip(&self.state.masses)
+                .fold(Energy::default(), |total, (vel, mass)| {
+                    total + *mass * vel.x * vel.y
+                });
+            (kinetic + self.pair_sums.virial_xy) / simulation_box.area()
+        })
+    }
+
+    /// The barostat in use, if configured
+    #[must_use]
+    pub fn barostat(&self) -> Option<&Barostat> {
+        self.barostat.as_ref()
+    }
+
+    /// Simulated time elapsed
+    #[must_use]
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Number of integrator steps taken
+    #[must_use]
+    pub fn step_count(&self) -> u64 {
+        self.step_count
+    }
+
+    /// Actual step sizes and rejections of the steps taken during the last `step_physics`
+    #[must_use]
+    pub fn step_reports(&self) -> &[StepReport] {
+        &self.step_reports
+    }
+
+    /// Replace the time integration scheme
+    pub fn set_integrator(&mut self, integrator: Box<dyn Integrator>) {
+        self.integrator = integrator;
+    }
+
+    /// Use periodic boundary conditions in the given box, or open boundaries if `None`.
+    /// Positions are wrapped into the box and image counters reset.
+    pub fn set_simulation_box(&mut self, simulation_box: Option<SimulationBox>) {
+        self.simulation_box = simulation_box;
+        self.images.fill((0, 0));
+        if let Some(simulation_box) = &self.simulation_box {
+            wrap_positions(simulation_box, &mut self.state, &mut self.images);
+        }
+        self.accelerations_current = false;
+    }
 
-        self.velocities[idx] =
-            (self.positions[idx] - prev_pos) / (Ratio::new::<ratio>(2.) * time_step);
+    #[must_use]
+    pub fn simulation_box(&self) -> Option<&SimulationBox> {
+        self.simulation_box.as_ref()
+    }
+
+    /// Current positions, wrapped into the simulation box if periodic
+    #[must_use]
+    pub fn positions(&self) -> &[Vector2D<Length>] {
+        &self.state.positions
+    }
+
+    #[must_use]
+    pub fn velocities(&self) -> &[Vector2D<Velocity>] {
+        &self.state.velocities
+    }
+
+    #[must_use]
+    pub fn masses(&self) -> &[Mass] {
+        &self.state.masses
+    }
+
+    #[must_use]
+    pub fn species(&self) -> &[Species] {
+        &self.state.species
+    }
 
-        self.last_positions[idx] = current_pos;
+    /// Center of mass of the unwrapped positions
+    #[must_use]
+    pub fn center_of_mass(&self) -> Vector2D<Length> {
+        center_of_mass(
+            self.unwrapped_positions()
+                .into_iter()
+                .zip(self.state.masses.iter().copied()),
+        )
     }
 
-    fn velocity_verlet_step(&mut self, idx: usize, time_step: Time) {
-        let current_pos = self.positions[idx];
-        let current_vel = self.velocities[idx];
+    /// Number of box lengths each particle has been wrapped by since the box was set
+    #[must_use]
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
 
-        self.positions[idx] += time_step * self.velocities[idx]
-            + (time_step / Ratio::new::<ratio>(2.0)) * (time_step * self.accelerations[idx]);
+    /// Positions as if no wrapping took place, e.g. for computing diffusion
+    #[must_use]
+    pub fn unwrapped_positions(&self) -> Vec<Vector2D<Length>> {
+        match &self.simulation_box {
+            Some(simulation_box) => self
+                .state
+                .positions
+                .iter()
+                .zip(&self.images)
+                .map(|(pos, image)| *pos + simulation_box.shift(*image))
+                .collect(),
+            None => self.state.positions.clone(),
+        }
+    }
+
+    /// Instantaneous temperature of the particles
+    #[must_use]
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        self.state.temperature()
+    }
 
-        self.velocities[idx] += (time_step / Ratio::new::<ratio>(2.0))
-            * (Ratio::new::<ratio>(2.0) * self.accelerations[idx]);
+    /// The thermostat in use, if configured
+    #[must_use]
+    pub fn thermostat(&self) -> Option<&Thermostat> {
+        self.thermostat.as_ref()
+    }
+
+    /// The neighbor list in use, if the potential has a cutoff
+    #[must_use]
+    pub fn neighbor_list(&self) -> Option<&NeighborList> {
+        self.neighbor_list.as_ref()
+    }
+}
 
-        self.last_positions[idx] = current_pos;
-        self.last_velocities[idx] = current_vel;
+/// Map positions back into the periodic box, keeping track of the images
+fn wrap_positions(simulation_box: &SimulationBox, state: &mut ParticleState, images: &mut [Image]) {
+    for (pos, image) in state.positions.iter_mut().zip(images) {
+        let (wrapped, (n_a, n_b)) = simulation_box.wrap(*pos);
+        *pos = wrapped;
+        image.0 += n_a;
+        image.1 += n_b;
     }
 }
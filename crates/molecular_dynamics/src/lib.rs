@@ -1,3 +1,8 @@
+pub mod analysis;
 pub mod handler;
+pub mod observables;
 pub mod physics;
 pub mod point_mass;
+pub mod random;
+pub mod scenario;
+pub mod trajectory;
@@ -0,0 +1,165 @@
+//! Diagnostics measured periodically during a simulation.
+//!
+//! An `Observable` is registered with the `SimulationHandler` together with an
+//! interval, and is handed a read-only view of the handler every that many steps.
+//! The built-in observables record a time series of `Sample`s, which can be read
+//! back through `SimulationHandler::observable`.
+
+use std::any::Any;
+
+use uom::si::{
+    angular_momentum::kilogram_square_meter_per_second,
+    f64::{AngularMomentum, Length, Momentum, ThermodynamicTemperature, Time},
+};
+
+use crate::handler::SimulationHandler;
+use physics_core::{quantities::Pressure2D, vector::Vector2D};
+
+/// A quantity measured from the state of a simulation
+pub trait Observable: Any {
+    /// Take a measurement of the current state
+    fn observe(&mut self, handler: &SimulationHandler);
+}
+
+/// A single measurement, with the step and time at which it was taken
+#[derive(Debug, Clone, Copy)]
+pub struct Sample<T> {
+    pub step: u64,
+    pub time: Time,
+    pub value: T,
+}
+
+impl<T> Sample<T> {
+    fn new(handler: &SimulationHandler, value: T) -> Self {
+        Self {
+            step: handler.step_count(),
+            time: handler.time(),
+            value,
+        }
+    }
+}
+
+/// Instantaneous temperature by 2D equipartition: K = N × k_B × T
+#[derive(Debug, Clone, Default)]
+pub struct Temperature {
+    samples: Vec<Sample<ThermodynamicTemperature>>,
+}
+
+impl Temperature {
+    #[must_use]
+    pub fn samples(&self) -> &[Sample<ThermodynamicTemperature>] {
+        &self.samples
+    }
+}
+
+impl Observable for Temperature {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        self.samples
+            .push(Sample::new(handler, handler.temperature()));
+    }
+}
+
+/// Instantaneous virial pressure; only recorded with a periodic simulation box
+#[derive(Debug, Clone, Default)]
+pub struct Pressure {
+    samples: Vec<Sample<Pressure2D>>,
+}
+
+impl Pressure {
+    #[must_use]
+    pub fn samples(&self) -> &[Sample<Pressure2D>] {
+        &self.samples
+    }
+}
+
+impl Observable for Pressure {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        if let Some(pressure) = handler.pressure() {
+            self.samples.push(Sample::new(handler, pressure));
+        }
+    }
+}
+
+/// Total linear momentum: Σ m × V
+#[derive(Debug, Clone, Default)]
+pub struct LinearMomentum {
+    samples: Vec<Sample<Vector2D<Momentum>>>,
+}
+
+impl LinearMomentum {
+    #[must_use]
+    pub fn samples(&self) -> &[Sample<Vector2D<Momentum>>] {
+        &self.samples
+    }
+}
+
+impl Observable for LinearMomentum {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        let momentum = handler
+            .velocities()
+            .iter()
+            .zip(handler.masses())
+            .fold(Vector2D::<Momentum>::zero(), |total, (vel, mass)| {
+                total + *mass * *vel
+            });
+        self.samples.push(Sample::new(handler, momentum));
+    }
+}
+
+/// Total angular momentum about the center of mass: Σ m × (R - R_cm) × V.
+/// Uses unwrapped positions, though it is not conserved in a periodic box.
+#[derive(Debug, Clone, Default)]
+pub struct TotalAngularMomentum {
+    samples: Vec<Sample<AngularMomentum>>,
+}
+
+impl TotalAngularMomentum {
+    #[must_use]
+    pub fn samples(&self) -> &[Sample<AngularMomentum>] {
+        &self.samples
+    }
+}
+
+impl Observable for TotalAngularMomentum {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        let center_of_mass = handler.center_of_mass();
+        let angular_momentum = handler
+            .unwrapped_positions()
+            .iter()
+            .zip(handler.velocities())
+            .zip(handler.masses())
+            .map(|((pos, vel), mass)| {
+                let r = *pos - center_of_mass;
+                *mass * (r.x * vel.y - r.y * vel.x)
+            })
+            .fold(0., |total, moment| total + moment.value);
+        // uom tracks angular momentum as a separate kind, so convert from SI base units
+        self.samples.push(Sample::new(
+            handler,
+            AngularMomentum::new::<kilogram_square_meter_per_second>(angular_momentum),
+        ));
+    }
+}
+
+/// Displacement of the center of mass since the first observation
+#[derive(Debug, Clone, Default)]
+pub struct CenterOfMassDrift {
+    initial: Option<Vector2D<Length>>,
+    samples: Vec<Sample<Vector2D<Length>>>,
+}
+
+impl CenterOfMassDrift {
+    #[must_use]
+    pub fn samples(&self) -> &[Sample<Vector2D<Length>>] {
+        &self.samples
+    }
+}
+
+impl Observable for CenterOfMassDrift {
+    fn observe(&mut self, handler: &SimulationHandler) {
+        let center_of_mass = handler.center_of_mass();
+        let initial = *self.initial.get_or_insert(center_of_mass);
+        self.samples
+            .push(Sample::new(handler, center_of_mass - initial));
+    }
+}
@@ -0,0 +1,152 @@
+//! Pressure control by rescaling the periodic simulation box.
+//!
+//! The pressure follows from the virial theorem, which in 2D reads
+//! P = (2K + W) / 2A, with K the kinetic energy, A the box area and W = Σ rᵢⱼ·Fᵢⱼ
+//! summed over all pairs. Box and positions are scaled together, keeping the
+//! fractional coordinates of the particles and the shape of the box fixed.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Area, Energy, Time},
+    ratio::ratio,
+};
+
+use crate::physics::{simulation_box::SimulationBox, time_integration::ParticleState};
+use physics_core::{
+    config::{BarostatConfig, BarostatKind},
+    constants::thermal_energy,
+    quantities::Pressure2D,
+};
+
+/// Virial pressure P = (2K + W) / 2A
+#[must_use]
+pub fn virial_pressure(kinetic_energy: Energy, virial: Energy, area: Area) -> Pressure2D {
+    (2. * kinetic_energy + virial) / (2. * area)
+}
+
+/// Barostat state, built from its configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Barostat {
+    config: BarostatConfig,
+    /// Steps since the last rescale or Monte Carlo move
+    steps: u32,
+    /// Time since the last Berendsen rescale
+    elapsed: Time,
+    attempted: usize,
+    accepted: usize,
+}
+
+impl Barostat {
+    #[must_use]
+    pub fn new(config: BarostatConfig) -> Self {
+        Self {
+            config,
+            steps: 0,
+            elapsed: Time::default(),
+            attempted: 0,
+            accepted: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &BarostatConfig {
+        &self.config
+    }
+
+    /// Number of Monte Carlo moves attempted
+    #[must_use]
+    pub fn attempted(&self) -> usize {
+        self.attempted
+    }
+
+    /// Number of Monte Carlo moves accepted
+    #[must_use]
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Couple the system to the pressure bath after a step of `time_step`,
+    /// given the current `pressure` and a function computing the potential energy
+    /// of a trial configuration. Returns whether box and positions were changed.
+    pub fn apply(
+        &mut self,
+        state: &mut ParticleState,
+        simulation_box: &mut SimulationBox,
+        pressure: Pressure2D,
+        time_step: Time,
+        mut potential_energy: impl FnMut(&ParticleState, &SimulationBox) -> Energy,
+        rng: &mut impl Rng,
+    ) -> bool {
+        match self.config.kind {
+            BarostatKind::Berendsen {
+                relaxation_time,
+                compressibility,
+                interval,
+            } => {
+                self.steps += 1;
+                self.elapsed += time_step;
+                if self.steps < interval {
+                    return false;
+                }
+                let elapsed = std::mem::take(&mut self.elapsed);
+                self.steps = 0;
+
+                // Area scales by 1 - β × τ/τ_P × (P₀ - P), edges by its square root
+                let area_scale = 1.
+                    - (compressibility * elapsed / relaxation_time
+                        * (self.config.target_pressure - pressure))
+                        .get::<ratio>();
+                rescale(state, simulation_box, area_scale.max(0.).sqrt());
+                true
+            }
+            BarostatKind::MonteCarlo {
+                temperature,
+                max_log_area_change,
+                interval,
+            } => {
+                self.steps += 1;
+                if self.steps < interval {
+                    return false;
+                }
+                self.steps = 0;
+                self.attempted += 1;
+
+                let old_area = simulation_box.area();
+                let old_energy = potential_energy(state, simulation_box);
+                let old_state = state.clone();
+                let old_box = *simulation_box;
+
+                let log_area_change = rng.random_range(-max_log_area_change..=max_log_area_change);
+                rescale(state, simulation_box, (log_area_change / 2.).exp());
+                let new_energy = potential_energy(state, simulation_box);
+
+                // Sampling ln(A) uniformly adds (N + 1) × ln(A′/A) to the acceptance
+                #[allow(clippy::cast_precision_loss)]
+                let entropy = (state.len() + 1) as f64 * log_area_change;
+                let enthalpy_change = new_energy - old_energy
+                    + self.config.target_pressure * (simulation_box.area() - old_area);
+                let exponent =
+                    entropy - (enthalpy_change / thermal_energy(temperature)).get::<ratio>();
+
+                if exponent >= 0. || rng.random::<f64>() < exponent.exp() {
+                    self.accepted += 1;
+                    true
+                } else {
+                    *state = old_state;
+                    *simulation_box = old_box;
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Scale the box edges by `factor` about its center, moving the particles along
+fn rescale(state: &mut ParticleState, simulation_box: &mut SimulationBox, factor: f64) {
+    let scaled_box = simulation_box.scaled(factor);
+    for pos in &mut state.positions {
+        *pos = scaled_box.cartesian(simulation_box.fractional(*pos));
+    }
+    *simulation_box = scaled_box;
+}
@@ -1,2 +1,6 @@
+pub mod barostat;
+pub mod neighbor_list;
 pub mod potential;
+pub mod simulation_box;
+pub mod thermostat;
 pub mod time_integration;
@@ -0,0 +1,214 @@
+//! Neighbor search for short-ranged potentials.
+//!
+//! Particles are binned into cells at least `cutoff + skin` wide, so that all
+//! partners of a particle are found in its own cell and the 8 surrounding ones.
+//! The resulting pair list (a Verlet list) is reused across steps until some
+//! particle has moved more than half the skin since the last rebuild.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Area, Length},
+    ratio::ratio,
+};
+
+use crate::physics::simulation_box::{SimulationBox, separation};
+use physics_core::vector::Vector2D;
+
+/// Cell list binning particle indices by position
+struct CellList<'a> {
+    cell_size: Length,
+    /// Periodic box and the number of cells along each of its edges
+    periodic: Option<(&'a SimulationBox, (i64, i64))>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl<'a> CellList<'a> {
+    /// Returns `None` if a periodic box is too small to hold 3 cells along each edge,
+    /// in which case cells would see the same neighbor twice
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(
+        cell_size: Length,
+        positions: &[Vector2D<Length>],
+        simulation_box: Option<&'a SimulationBox>,
+    ) -> Option<Self> {
+        let periodic = match simulation_box {
+            Some(simulation_box) => {
+                let (width_a, width_b) = simulation_box.perpendicular_widths();
+                let counts = (
+                    (width_a / cell_size).get::<ratio>().floor() as i64,
+                    (width_b / cell_size).get::<ratio>().floor() as i64,
+                );
+                if counts.0 < 3 || counts.1 < 3 {
+                    return None;
+                }
+                Some((simulation_box, counts))
+            }
+            None => None,
+        };
+
+        let mut cell_list = Self {
+            cell_size,
+            periodic,
+            cells: HashMap::new(),
+        };
+        for (idx, pos) in positions.iter().enumerate() {
+            let cell = cell_list.cell_of(*pos);
+            cell_list.cells.entry(cell).or_default().push(idx);
+        }
+        Some(cell_list)
+    }
+
+    /// Index of the cell containing `pos`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn cell_of(&self, pos: Vector2D<Length>) -> (i64, i64) {
+        match self.periodic {
+            Some((simulation_box, (n_a, n_b))) => {
+                let (s_x, s_y) = simulation_box.fractional(pos);
+                (
+                    ((s_x * n_a as f64).floor() as i64).rem_euclid(n_a),
+                    ((s_y * n_b as f64).floor() as i64).rem_euclid(n_b),
+                )
+            }
+            None => (
+                (pos.x / self.cell_size).get::<ratio>().floor() as i64,
+                (pos.y / self.cell_size).get::<ratio>().floor() as i64,
+            ),
+        }
+    }
+
+    /// Particle indices in the given cell and its 8 surrounding cells
+    fn neighborhood(&self, (cx, cy): (i64, i64)) -> impl Iterator<Item = usize> + '_ {
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dy| (cx + dx, cy + dy)))
+            .map(|(x, y)| match self.periodic {
+                Some((_, (n_a, n_b))) => (x.rem_euclid(n_a), y.rem_euclid(n_b)),
+                None => (x, y),
+            })
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
+
+/// Verlet neighbor list with automatic rebuild on maximum displacement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborList {
+    cutoff: Length,
+    skin: Length,
+    pairs: Vec<(usize, usize)>,
+    reference_positions: Vec<Vector2D<Length>>,
+    reference_box: Option<SimulationBox>,
+    rebuilds: usize,
+}
+
+impl NeighborList {
+    /// Initialise an empty list for the given interaction cutoff and skin thickness
+    #[must_use]
+    pub fn new(cutoff: Length, skin: Length) -> Self {
+        Self {
+            cutoff,
+            skin,
+            pairs: Vec::new(),
+            reference_positions: Vec::new(),
+            reference_box: None,
+            rebuilds: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn cutoff(&self) -> Length {
+        self.cutoff
+    }
+
+    #[must_use]
+    pub fn skin(&self) -> Length {
+        self.skin
+    }
+
+    /// All pairs `(i, j)` with `i < j` that were within `cutoff + skin` at the last rebuild
+    #[must_use]
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    /// Number of times the list has been rebuilt
+    #[must_use]
+    pub fn rebuilds(&self) -> usize {
+        self.rebuilds
+    }
+
+    /// Rebuild the list if required for the given positions.
+    /// Returns whether a rebuild took place.
+    pub fn update(
+        &mut self,
+        positions: &[Vector2D<Length>],
+        simulation_box: Option<&SimulationBox>,
+    ) -> bool {
+        if self.needs_rebuild(positions, simulation_box) {
+            self.rebuild(positions, simulation_box);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The list is stale once any particle moved more than half the skin,
+    /// as two particles approaching each other could then have crossed the cutoff
+    fn needs_rebuild(
+        &self,
+        positions: &[Vector2D<Length>],
+        simulation_box: Option<&SimulationBox>,
+    ) -> bool {
+        if positions.len() != self.reference_positions.len()
+            || simulation_box != self.reference_box.as_ref()
+        {
+            return true;
+        }
+
+        let half_skin = self.skin / 2.;
+        let threshold: Area = half_skin * half_skin;
+        positions
+            .iter()
+            .zip(&self.reference_positions)
+            .any(|(pos, reference)| {
+                let d = separation(*pos, *reference, simulation_box);
+                d.x * d.x + d.y * d.y >= threshold
+            })
+    }
+
+    fn rebuild(&mut self, positions: &[Vector2D<Length>], simulation_box: Option<&SimulationBox>) {
+        let range = self.cutoff + self.skin;
+        let range_squared: Area = range * range;
+        let within_range = |i: usize, j: usize| {
+            let d = separation(positions[i], positions[j], simulation_box);
+            d.x * d.x + d.y * d.y < range_squared
+        };
+
+        self.pairs.clear();
+        if let Some(cell_list) = CellList::new(range, positions, simulation_box) {
+            for (i, pos) in positions.iter().enumerate() {
+                for j in cell_list.neighborhood(cell_list.cell_of(*pos)) {
+                    if i < j && within_range(i, j) {
+                        self.pairs.push((i, j));
+                    }
+                }
+            }
+        } else {
+            // Box too small for cells to help, check all pairs
+            for i in 0..positions.len() {
+                for j in (i + 1)..positions.len() {
+                    if within_range(i, j) {
+                        self.pairs.push((i, j));
+                    }
+                }
+            }
+        }
+
+        self.reference_positions.clear();
+        self.reference_positions.extend_from_slice(positions);
+        self.reference_box = simulation_box.copied();
+        self.rebuilds += 1;
+    }
+}
@@ -1,17 +1,22 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
 use uom::si::{
     ISQ, Quantity, SI,
     energy::electronvolt,
-    f64::{Acceleration, Energy, Force, Length, Mass, Ratio, Time, Velocity},
+    f64::{Acceleration, Area, Energy, Force, Length, Mass, Ratio, Time, Velocity},
     length::{angstrom, meter},
     mass::kilogram,
     ratio::ratio,
     time::second,
 };
-use uom::typenum::{N1, N2, P2, P3, P6, P8, P12, P14, Z0};
-use visualization::simulation::config::SimulationConfig;
+use uom::typenum::{N1, N2, P2, P3, P4, P6, P10, P12, Z0};
 
-use crate::point_mass::PointMass;
-use physics_core::vector::Vector2D;
+use crate::{
+    physics::simulation_box::{SimulationBox, separation},
+    point_mass::{PointMass, Species},
+};
+use physics_core::{config::SimulationConfig, quantities::Pressure2D, vector::Vector2D};
 
 // ----- HELPER FUNCTIONS -----
 
@@ -55,17 +60,39 @@ pub trait Potential {
         config: &SimulationConfig,
     ) -> Vector2D<Force>;
 
-    /// Compute pair-wise force from provided arrays
+    /// Compute pair-wise force from provided arrays.
+    /// If a simulation box is given, the minimum-image separation is used.
+    #[allow(clippy::too_many_arguments)]
     fn force_from_arrays(
         &self,
         idx1: usize,
         idx2: usize,
-        pos_arr: &Vec<Vector2D<Length>>,
-        vel_arr: &Vec<Vector2D<Velocity>>,
-        acc_arr: &Vec<Vector2D<Acceleration>>,
-        mass_arr: &Vec<Mass>,
+        pos_arr: &[Vector2D<Length>],
+        vel_arr: &[Vector2D<Velocity>],
+        acc_arr: &[Vector2D<Acceleration>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
         config: &SimulationConfig,
     ) -> Vector2D<Force>;
+
+    /// Compute pair-wise potential energy from provided arrays.
+    /// If a simulation box is given, the minimum-image separation is used.
+    fn energy_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+    ) -> Energy;
+
+    /// Distance beyond which the pair interaction vanishes, if finite.
+    /// Potentials with a cutoff are evaluated using neighbor lists.
+    fn cutoff(&self) -> Option<Length> {
+        None
+    }
 }
 
 // ----- GRAVITY POTENTIAL -----
@@ -79,15 +106,23 @@ pub type GravitationalParameter = Quantity<
 
 /// Newtonian Gravity potential.
 /// Typical value for the Gravitational Constant is G = 6.67430×10⁻¹¹ m³·kg⁻¹·s⁻²
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Gravity {
+    /// In SI base units when read from a file; defaults to the gravitational constant
+    #[serde(default = "gravitational_constant")]
     pub big_g: GravitationalParameter,
 }
 
+/// Gravitational constant G = 6.67430×10⁻¹¹ m³·kg⁻¹·s⁻²
+fn gravitational_constant() -> GravitationalParameter {
+    6.67430e-11 * Length::new::<meter>(1.0).powi(P3::new())
+        / (Mass::new::<kilogram>(1.0) * Time::new::<second>(1.0).powi(P2::new()))
+}
+
 impl Potential for Gravity {
     fn default() -> Self {
         Self {
-            big_g: 6.67430e-11 * Length::new::<meter>(1.0).powi(P3::new())
-                / (Mass::new::<kilogram>(1.0) * Time::new::<second>(1.0).powi(P2::new())),
+            big_g: gravitational_constant(),
         }
     }
 
@@ -119,13 +154,15 @@ impl Potential for Gravity {
         &self,
         idx1: usize,
         idx2: usize,
-        pos_arr: &Vec<Vector2D<Length>>,
-        _vel_arr: &Vec<Vector2D<Velocity>>,
-        _acc_arr: &Vec<Vector2D<Acceleration>>,
-        mass_arr: &Vec<Mass>,
+        pos_arr: &[Vector2D<Length>],
+        _vel_arr: &[Vector2D<Velocity>],
+        _acc_arr: &[Vector2D<Acceleration>],
+        mass_arr: &[Mass],
+        _species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
         config: &SimulationConfig,
     ) -> Vector2D<Force> {
-        let r: Vector2D<Length> = pos_arr[idx1] - pos_arr[idx2];
+        let r: Vector2D<Length> = separation(pos_arr[idx1], pos_arr[idx2], simulation_box);
         let r_mag: Length = soften_distance(r.mag(), config);
         let r_hat: Vector2D<Ratio> = r / r_mag;
 
@@ -133,72 +170,623 @@ impl Potential for Gravity {
 
         cap_force(force, config)
     }
+
+    /// Gravitational potential energy: U = -G·m₁·m₂/r
+    fn energy_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        mass_arr: &[Mass],
+        _species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+    ) -> Energy {
+        let r = separation(pos_arr[idx1], pos_arr[idx2], simulation_box);
+        -self.big_g * mass_arr[idx1] * mass_arr[idx2] / r.mag()
+    }
 }
 
 // ----- LENNARD-JONES POTENTIAL -----
 
+/// Treatment of a pair potential at its cutoff radius
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CutoffMode {
+    /// Energy and force are set to zero beyond the cutoff: U(r) for r < r_c
+    Truncated,
+    /// Truncated, with the energy shifted to be continuous: U(r) - U(r_c)
+    Shifted,
+    /// Energy and force both continuous: U(r) - U(r_c) + (r - r_c)·F(r_c)
+    ShiftedForce,
+}
+
+/// Cutoff radius and the mode used to apply it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Cutoff {
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+    pub radius: Length,
+    pub mode: CutoffMode,
+}
+
 /// The Lennard-Jones potential, commonly used in molecular dynamics
 /// Typical value examples
 /// Xenon: ε = 0.0184 eV, σ = 4.10 Å
 /// Argon: ε = 0.0104 eV (or `ε/k_B` = 119.8 K), σ = 3.40 Å
+/// A common choice of cutoff is r_c = 2.5σ
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct LennardJones {
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
     pub epsilon: Energy,
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
     pub sigma: Length,
+    /// If given, interactions beyond the cutoff radius are neglected
+    #[serde(default)]
+    pub cutoff: Option<Cutoff>,
+}
+
+impl LennardJones {
+    /// Returns the potential with the given cutoff applied
+    #[must_use]
+    pub fn with_cutoff(self, radius: Length, mode: CutoffMode) -> Self {
+        Self {
+            cutoff: Some(Cutoff { radius, mode }),
+            ..self
+        }
+    }
+
+    /// Unmodified pair energy: U = 4ε[(σ/r)¹² - (σ/r)⁶]
+    fn pair_energy(&self, r_mag: Length) -> Energy {
+        Ratio::new::<ratio>(4.0)
+            * self.epsilon
+            * ((self.sigma / r_mag).powi(P12::new()) - (self.sigma / r_mag).powi(P6::new()))
+    }
+
+    /// Unmodified repulsive force magnitude: F = (24ε/r)[2(σ/r)¹² - (σ/r)⁶]
+    fn pair_force_magnitude(&self, r_mag: Length) -> Force {
+        Ratio::new::<ratio>(24.0) * self.epsilon / r_mag
+            * (Ratio::new::<ratio>(2.0) * (self.sigma / r_mag).powi(P12::new())
+                - (self.sigma / r_mag).powi(P6::new()))
+    }
+
+    /// Pair energy with the cutoff applied
+    fn cutoff_energy(&self, r_mag: Length) -> Energy {
+        let Some(Cutoff { radius, mode }) = self.cutoff else {
+            return self.pair_energy(r_mag);
+        };
+        if r_mag >= radius {
+            return Energy::default();
+        }
+
+        match mode {
+            CutoffMode::Truncated => self.pair_energy(r_mag),
+            CutoffMode::Shifted => self.pair_energy(r_mag) - self.pair_energy(radius),
+            CutoffMode::ShiftedForce => {
+                self.pair_energy(r_mag) - self.pair_energy(radius)
+                    + (r_mag - radius) * self.pair_force_magnitude(radius)
+            }
+        }
+    }
+
+    /// Force exerted on the point at separation `r` from the other, with the cutoff applied
+    fn cutoff_force(&self, r: Vector2D<Length>, config: &SimulationConfig) -> Vector2D<Force> {
+        let distance = r.mag();
+        if let Some(cutoff) = self.cutoff
+            && distance >= cutoff.radius
+        {
+            return Vector2D::<Force>::zero();
+        }
+
+        let r_hat: Vector2D<Ratio> = r / distance;
+        let mut magnitude = self.pair_force_magnitude(soften_distance(distance, config));
+        if let Some(Cutoff {
+            radius,
+            mode: CutoffMode::ShiftedForce,
+        }) = self.cutoff
+        {
+            magnitude -= self.pair_force_magnitude(radius);
+        }
+
+        cap_force(r_hat * magnitude, config)
+    }
+
+    /// Long-range correction to the total energy of `particle_count` particles in `area`,
+    /// assuming a uniform distribution beyond the cutoff:
+    /// `U_tail` = 4πNρεσ²[(σ/r_c)¹⁰/10 - (σ/r_c)⁴/4]
+    ///
+    /// Only meaningful for the truncated and shifted modes; zero without a cutoff.
+    #[must_use]
+    pub fn energy_tail_correction(&self, particle_count: usize, area: Area) -> Energy {
+        let Some(cutoff) = self.cutoff else {
+            return Energy::default();
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let n = particle_count as f64;
+        let density = n / area;
+        let sigma_over_rc = self.sigma / cutoff.radius;
+
+        Ratio::new::<ratio>(4.0 * PI * n)
+            * density
+            * self.epsilon
+            * self.sigma
+            * self.sigma
+            * (sigma_over_rc.powi(P10::new()) / 10.0 - sigma_over_rc.powi(P4::new()) / 4.0)
+    }
+
+    /// Long-range correction to the (2D) pressure of `particle_count` particles in `area`,
+    /// assuming a uniform distribution beyond the cutoff:
+    /// `P_tail` = πρ²εσ²[12/5·(σ/r_c)¹⁰ - 3(σ/r_c)⁴]
+    ///
+    /// Zero without a cutoff.
+    #[must_use]
+    pub fn pressure_tail_correction(&self, particle_count: usize, area: Area) -> Pressure2D {
+        let Some(cutoff) = self.cutoff else {
+            return Pressure2D::default();
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let density = particle_count as f64 / area;
+        let sigma_over_rc = self.sigma / cutoff.radius;
+
+        Ratio::new::<ratio>(PI)
+            * density
+            * density
+            * self.epsilon
+            * self.sigma
+            * self.sigma
+            * (Ratio::new::<ratio>(12.0 / 5.0) * sigma_over_rc.powi(P10::new())
+                - Ratio::new::<ratio>(3.0) * sigma_over_rc.powi(P4::new()))
+    }
 }
 
 impl Potential for LennardJones {
-    /// Returns `LennardJones` with parameters for Argon gas
+    /// Returns `LennardJones` with parameters for Argon gas, without cutoff
     fn default() -> Self {
         Self {
             epsilon: Energy::new::<electronvolt>(0.0104),
             sigma: Length::new::<angstrom>(3.4),
+            cutoff: None,
         }
     }
 
-    /// Lennard-Jones potential energy: U = 4ε[(σ/r)¹² - (σ/r)⁶]
+    /// Lennard-Jones potential energy: U = 4ε[(σ/r)¹² - (σ/r)⁶], modified by the cutoff
     fn energy(&self, point1: &PointMass, point2: &PointMass) -> Energy {
         let r: Vector2D<Length> = point2.pos() - point1.pos();
-        Ratio::new::<ratio>(4.0)
-            * self.epsilon
-            * ((self.sigma / r.mag()).powi(P12::new()) - (self.sigma / r.mag()).powi(P6::new()))
+        self.cutoff_energy(r.mag())
     }
 
-    /// Lennard-Jones force: F = (48ε/σ²)·r·[(σ/r)¹⁴ - 0.5(σ/r)⁸]
+    /// Lennard-Jones force: F = (24ε/r)·r̂·[2(σ/r)¹² - (σ/r)⁶], modified by the cutoff
     fn force(
         &self,
         point1: &PointMass,
         point2: &PointMass,
         config: &SimulationConfig,
     ) -> Vector2D<Force> {
-        let r: Vector2D<Length> = point1.pos() - point2.pos();
-        let r_mag = soften_distance(r.mag(), config);
+        self.cutoff_force(point1.pos() - point2.pos(), config)
+    }
 
-        let force: Vector2D<Force> = r * (Ratio::new::<ratio>(48.) * self.epsilon)
-            / (self.sigma * self.sigma)
-            * ((self.sigma / r_mag).powi(P14::new())
-                - Ratio::new::<ratio>(0.5) * (self.sigma / r_mag).powi(P8::new()));
+    /// Lennard-Jones force: F = (24ε/r)·r̂·[2(σ/r)¹² - (σ/r)⁶], modified by the cutoff
+    fn force_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        _vel_arr: &[Vector2D<Velocity>],
+        _acc_arr: &[Vector2D<Acceleration>],
+        _mass_arr: &[Mass],
+        _species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+        config: &SimulationConfig,
+    ) -> Vector2D<Force> {
+        self.cutoff_force(
+            separation(pos_arr[idx1], pos_arr[idx2], simulation_box),
+            config,
+        )
+    }
 
-        cap_force(force, config)
+    /// Lennard-Jones potential energy: U = 4ε[(σ/r)¹² - (σ/r)⁶], modified by the cutoff
+    fn energy_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        _mass_arr: &[Mass],
+        _species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+    ) -> Energy {
+        self.cutoff_energy(separation(pos_arr[idx1], pos_arr[idx2], simulation_box).mag())
+    }
+
+    fn cutoff(&self) -> Option<Length> {
+        self.cutoff.map(|cutoff| cutoff.radius)
+    }
+}
+
+// ----- PER-SPECIES PAIR POTENTIALS -----
+
+/// A pair potential with its own parameters for every pair of species, e.g. for
+/// binary mixtures. The potential of a pair is looked up by the species of its two
+/// particles, which must be less than `species_count`.
+#[derive(Debug, Clone)]
+pub struct PairTable<P> {
+    species_count: usize,
+    /// Symmetric, row by row
+    pairs: Vec<P>,
+}
+
+impl<P: Clone> PairTable<P> {
+    /// Table for `species_count` species, with the potential between species `a <= b`
+    /// given by `potential(a, b)`
+    #[must_use]
+    pub fn from_fn(species_count: usize, mut potential: impl FnMut(Species, Species) -> P) -> Self {
+        let mut pairs: Vec<P> = Vec::with_capacity(species_count * species_count);
+        for a in 0..species_count {
+            for b in 0..species_count {
+                let pair = if b < a {
+                    pairs[b * species_count + a].clone()
+                } else {
+                    potential(a, b)
+                };
+                pairs.push(pair);
+            }
+        }
+        Self {
+            species_count,
+            pairs,
+        }
+    }
+
+    /// Returns the table with the potential between species `a` and `b` replaced,
+    /// e.g. to override a mixing rule
+    ///
+    /// # Panics
+    /// Panics if either species is out of range.
+    #[must_use]
+    pub fn with_pair(mut self, a: Species, b: Species, potential: P) -> Self {
+        let index = self.index(a, b);
+        self.pairs[index] = potential.clone();
+        let index = self.index(b, a);
+        self.pairs[index] = potential;
+        self
+    }
+
+    #[must_use]
+    pub fn species_count(&self) -> usize {
+        self.species_count
+    }
+
+    /// Potential between species `a` and `b`
+    ///
+    /// # Panics
+    /// Panics if either species is out of range.
+    #[must_use]
+    pub fn get(&self, a: Species, b: Species) -> &P {
+        &self.pairs[self.index(a, b)]
+    }
+
+    fn index(&self, a: Species, b: Species) -> usize {
+        assert!(
+            a < self.species_count && b < self.species_count,
+            "species {a} or {b} out of range for a table of {} species",
+            self.species_count
+        );
+        a * self.species_count + b
+    }
+}
+
+/// Rule for the Lennard-Jones parameters between unlike species
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MixingRule {
+    /// σᵢⱼ = (σᵢ + σⱼ)/2, εᵢⱼ = √(εᵢ·εⱼ)
+    #[default]
+    LorentzBerthelot,
+    /// σᵢⱼ = √(σᵢ·σⱼ), εᵢⱼ = √(εᵢ·εⱼ)
+    Geometric,
+}
+
+impl MixingRule {
+    /// Parameters between particles with parameters `a` and `b`. Cutoff radii are
+    /// mixed like σ, using the mode of `a`; if either has no cutoff, neither does the pair.
+    #[must_use]
+    pub fn mix(self, a: &LennardJones, b: &LennardJones) -> LennardJones {
+        let mean = |x: Length, y: Length| match self {
+            Self::LorentzBerthelot => (x + y) / 2.,
+            Self::Geometric => (x * y).sqrt(),
+        };
+        LennardJones {
+            epsilon: (a.epsilon * b.epsilon).sqrt(),
+            sigma: mean(a.sigma, b.sigma),
+            cutoff: match (a.cutoff, b.cutoff) {
+                (Some(cutoff_a), Some(cutoff_b)) => Some(Cutoff {
+                    radius: mean(cutoff_a.radius, cutoff_b.radius),
+                    mode: cutoff_a.mode,
+                }),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl PairTable<LennardJones> {
+    /// Table for species with the like-pair parameters `species`, and unlike pairs
+    /// mixed by `rule`
+    #[must_use]
+    pub fn mixed(species: &[LennardJones], rule: MixingRule) -> Self {
+        Self::from_fn(species.len(), |a, b| {
+            if a == b {
+                species[a]
+            } else {
+                rule.mix(&species[a], &species[b])
+            }
+        })
+    }
+}
+
+impl<P: Potential + Clone> Potential for PairTable<P> {
+    /// Returns a table of a single species with the default potential
+    fn default() -> Self {
+        Self::from_fn(1, |_, _| P::default())
+    }
+
+    fn energy(&self, point1: &PointMass, point2: &PointMass) -> Energy {
+        self.get(point1.species(), point2.species())
+            .energy(point1, point2)
+    }
+
+    fn force(
+        &self,
+        point1: &PointMass,
+        point2: &PointMass,
+        config: &SimulationConfig,
+    ) -> Vector2D<Force> {
+        self.get(point1.species(), point2.species())
+            .force(point1, point2, config)
     }
 
-    /// Lennard-Jones force: F = (48ε/σ²)·r·[(σ/r)¹⁴ - 0.5(σ/r)⁸]
     fn force_from_arrays(
         &self,
         idx1: usize,
         idx2: usize,
-        pos_arr: &Vec<Vector2D<Length>>,
-        _vel_arr: &Vec<Vector2D<Velocity>>,
-        _acc_arr: &Vec<Vector2D<Acceleration>>,
-        _mass_arr: &Vec<Mass>,
+        pos_arr: &[Vector2D<Length>],
+        vel_arr: &[Vector2D<Velocity>],
+        acc_arr: &[Vector2D<Acceleration>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
         config: &SimulationConfig,
     ) -> Vector2D<Force> {
-        let r: Vector2D<Length> = pos_arr[idx1] - pos_arr[idx2];
-        let r_mag: Length = soften_distance(r.mag(), config);
+        self.get(species_arr[idx1], species_arr[idx2])
+            .force_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                vel_arr,
+                acc_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+                config,
+            )
+    }
 
-        let force = r * (Ratio::new::<ratio>(48.) * self.epsilon) / (self.sigma * self.sigma)
-            * ((self.sigma / r_mag).powi(P14::new())
-                - Ratio::new::<ratio>(0.5) * (self.sigma / r_mag).powi(P8::new()));
+    fn energy_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+    ) -> Energy {
+        self.get(species_arr[idx1], species_arr[idx2])
+            .energy_from_arrays(idx1, idx2, pos_arr, mass_arr, species_arr, simulation_box)
+    }
 
-        cap_force(force, config)
+    /// The largest cutoff of all pairs, if every pair has one
+    fn cutoff(&self) -> Option<Length> {
+        self.pairs
+            .iter()
+            .map(Potential::cutoff)
+            .try_fold(Length::default(), |largest, cutoff| {
+                cutoff.map(|cutoff| largest.max(cutoff))
+            })
+    }
+}
+
+/// A Lennard-Jones table as written in files: parameters of like pairs per species,
+/// the rule mixing them for unlike pairs, and unlike pairs overriding the rule
+#[derive(Serialize, Deserialize)]
+struct LennardJonesMixture {
+    species: Vec<LennardJones>,
+    #[serde(default)]
+    mixing: MixingRule,
+    #[serde(default)]
+    pairs: Vec<MixturePair>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MixturePair {
+    species: [Species; 2],
+    parameters: LennardJones,
+}
+
+/// Written with the parameters of every unlike pair, so no mixing rule is needed
+impl Serialize for PairTable<LennardJones> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let n = self.species_count;
+        LennardJonesMixture {
+            species: (0..n).map(|a| *self.get(a, a)).collect(),
+            mixing: MixingRule::default(),
+            pairs: (0..n)
+                .flat_map(|a| ((a + 1)..n).map(move |b| (a, b)))
+                .map(|(a, b)| MixturePair {
+                    species: [a, b],
+                    parameters: *self.get(a, b),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PairTable<LennardJones> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mixture = LennardJonesMixture::deserialize(deserializer)?;
+        let n = mixture.species.len();
+        let mut table = Self::mixed(&mixture.species, mixture.mixing);
+        for MixturePair {
+            species: [a, b],
+            parameters,
+        } in mixture.pairs
+        {
+            if a >= n || b >= n {
+                return Err(serde::de::Error::custom(format!(
+                    "pair of species {a} and {b}, but only {n} species are given"
+                )));
+            }
+            table = table.with_pair(a, b, parameters);
+        }
+        Ok(table)
+    }
+}
+
+// ----- ANY BUILT-IN POTENTIAL -----
+
+/// One of the built-in potentials, chosen at runtime, e.g. when read from a scenario file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PairPotential {
+    Gravity(Gravity),
+    LennardJones(LennardJones),
+    /// Lennard-Jones parameters per pair of species
+    LennardJonesMixture(PairTable<LennardJones>),
+}
+
+impl PairPotential {
+    /// Number of species the potential has parameters for, if it distinguishes them
+    #[must_use]
+    pub fn species_count(&self) -> Option<usize> {
+        match self {
+            Self::Gravity(_) | Self::LennardJones(_) => None,
+            Self::LennardJonesMixture(table) => Some(table.species_count()),
+        }
+    }
+}
+
+impl Potential for PairPotential {
+    /// Returns the default `LennardJones` potential
+    fn default() -> Self {
+        Self::LennardJones(LennardJones::default())
+    }
+
+    fn energy(&self, point1: &PointMass, point2: &PointMass) -> Energy {
+        match self {
+            Self::Gravity(potential) => potential.energy(point1, point2),
+            Self::LennardJones(potential) => potential.energy(point1, point2),
+            Self::LennardJonesMixture(potential) => potential.energy(point1, point2),
+        }
+    }
+
+    fn force(
+        &self,
+        point1: &PointMass,
+        point2: &PointMass,
+        config: &SimulationConfig,
+    ) -> Vector2D<Force> {
+        match self {
+            Self::Gravity(potential) => potential.force(point1, point2, config),
+            Self::LennardJones(potential) => potential.force(point1, point2, config),
+            Self::LennardJonesMixture(potential) => potential.force(point1, point2, config),
+        }
+    }
+
+    fn force_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        vel_arr: &[Vector2D<Velocity>],
+        acc_arr: &[Vector2D<Acceleration>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+        config: &SimulationConfig,
+    ) -> Vector2D<Force> {
+        match self {
+            Self::Gravity(potential) => potential.force_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                vel_arr,
+                acc_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+                config,
+            ),
+            Self::LennardJones(potential) => potential.force_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                vel_arr,
+                acc_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+                config,
+            ),
+            Self::LennardJonesMixture(potential) => potential.force_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                vel_arr,
+                acc_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+                config,
+            ),
+        }
+    }
+
+    fn energy_from_arrays(
+        &self,
+        idx1: usize,
+        idx2: usize,
+        pos_arr: &[Vector2D<Length>],
+        mass_arr: &[Mass],
+        species_arr: &[Species],
+        simulation_box: Option<&SimulationBox>,
+    ) -> Energy {
+        match self {
+            Self::Gravity(potential) => potential.energy_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+            ),
+            Self::LennardJones(potential) => potential.energy_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+            ),
+            Self::LennardJonesMixture(potential) => potential.energy_from_arrays(
+                idx1,
+                idx2,
+                pos_arr,
+                mass_arr,
+                species_arr,
+                simulation_box,
+            ),
+        }
+    }
+
+    fn cutoff(&self) -> Option<Length> {
+        match self {
+            Self::Gravity(potential) => potential.cutoff(),
+            Self::LennardJones(potential) => potential.cutoff(),
+            Self::LennardJonesMixture(potential) => potential.cutoff(),
+        }
     }
 }
@@ -0,0 +1,171 @@
+//! Periodic simulation box.
+//!
+//! The box is spanned by the edge vectors a = (lx, 0) and b = (xy, ly), starting
+//! from `origin`. A zero tilt `xy` gives an orthorhombic (rectangular) box, any
+//! other value a triclinic one. Tilts are expected to satisfy |xy| ≤ lx/2, for
+//! which the minimum-image convention reduces to rounding fractional coordinates.
+
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Area, Length},
+    ratio::ratio,
+};
+
+use crate::point_mass::cluster::RectangularBounds;
+use physics_core::vector::Vector2D;
+
+/// Number of box lengths a position is shifted by when wrapped, along a and b
+pub type Image = (i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SimulationBox {
+    /// Lower-left corner of the box
+    #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+    pub origin: Vector2D<Length>,
+    /// Length of the first edge vector, along x
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+    pub lx: Length,
+    /// Height of the box, along y
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+    pub ly: Length,
+    /// Tilt factor: x-component of the second edge vector
+    #[serde(
+        default,
+        deserialize_with = "physics_core::quantity_serde::deserialize"
+    )]
+    pub xy: Length,
+}
+
+impl SimulationBox {
+    /// Rectangular box with the lower-left corner at `origin`
+    #[must_use]
+    pub fn orthorhombic(origin: Vector2D<Length>, lx: Length, ly: Length) -> Self {
+        Self {
+            origin,
+            lx,
+            ly,
+            xy: Length::default(),
+        }
+    }
+
+    /// Parallelogram box with edge vectors (lx, 0) and (xy, ly)
+    #[must_use]
+    pub fn triclinic(origin: Vector2D<Length>, lx: Length, ly: Length, xy: Length) -> Self {
+        Self { origin, lx, ly, xy }
+    }
+
+    #[must_use]
+    pub fn is_orthorhombic(&self) -> bool {
+        self.xy == Length::default()
+    }
+
+    #[must_use]
+    pub fn area(&self) -> Area {
+        self.lx * self.ly
+    }
+
+    /// Width of the box perpendicular to each pair of edges, i.e. the largest
+    /// separations for which the minimum image is unique (when halved)
+    #[must_use]
+    pub fn perpendicular_widths(&self) -> (Length, Length) {
+        let b_mag = Vector2D {
+            x: self.xy,
+            y: self.ly,
+        }
+        .mag();
+        (self.area() / b_mag, self.ly)
+    }
+
+    /// Position in fractional coordinates of the box, in [0, 1) inside the box
+    #[must_use]
+    pub fn fractional(&self, pos: Vector2D<Length>) -> (f64, f64) {
+        let d = pos - self.origin;
+        let s_y = (d.y / self.ly).get::<ratio>();
+        let s_x = ((d.x - self.xy * s_y) / self.lx).get::<ratio>();
+        (s_x, s_y)
+    }
+
+    /// Position of the given fractional coordinates, the inverse of `fractional`
+    #[must_use]
+    pub fn cartesian(&self, (s_x, s_y): (f64, f64)) -> Vector2D<Length> {
+        self.origin
+            + Vector2D {
+                x: self.lx * s_x + self.xy * s_y,
+                y: self.ly * s_y,
+            }
+    }
+
+    /// Box with all edges scaled by `factor` about its center, keeping its shape
+    #[must_use]
+    pub fn scaled(&self, factor: f64) -> Self {
+        let center = self.cartesian((0.5, 0.5));
+        Self {
+            origin: center + (self.origin - center) * factor,
+            lx: self.lx * factor,
+            ly: self.ly * factor,
+            xy: self.xy * factor,
+        }
+    }
+
+    /// Displacement corresponding to shifting by the given number of box lengths
+    #[must_use]
+    pub fn shift(&self, (n_a, n_b): Image) -> Vector2D<Length> {
+        #[allow(clippy::cast_precision_loss)]
+        let (n_a, n_b) = (n_a as f64, n_b as f64);
+        Vector2D {
+            x: self.lx * n_a + self.xy * n_b,
+            y: self.ly * n_b,
+        }
+    }
+
+    /// Map a position back into the box.
+    /// Returns the wrapped position and the number of box lengths it was moved by.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn wrap(&self, pos: Vector2D<Length>) -> (Vector2D<Length>, Image) {
+        let (s_x, s_y) = self.fractional(pos);
+        let image = (s_x.floor() as i64, s_y.floor() as i64);
+        (pos - self.shift(image), image)
+    }
+
+    /// Shortest periodic image of a displacement vector
+    #[must_use]
+    pub fn minimum_image(&self, d: Vector2D<Length>) -> Vector2D<Length> {
+        let n_b = (d.y / self.ly).get::<ratio>().round();
+        let d = Vector2D {
+            x: d.x - self.xy * n_b,
+            y: d.y - self.ly * n_b,
+        };
+        let n_a = (d.x / self.lx).get::<ratio>().round();
+        Vector2D {
+            x: d.x - self.lx * n_a,
+            y: d.y,
+        }
+    }
+}
+
+impl From<&RectangularBounds> for SimulationBox {
+    fn from(bounds: &RectangularBounds) -> Self {
+        Self::orthorhombic(
+            Vector2D {
+                x: bounds.x1.min(bounds.x2),
+                y: bounds.y1.min(bounds.y2),
+            },
+            (bounds.x2 - bounds.x1).abs(),
+            (bounds.y2 - bounds.y1).abs(),
+        )
+    }
+}
+
+/// Displacement from `pos2` to `pos1`, using the minimum image if periodic
+#[must_use]
+pub fn separation(
+    pos1: Vector2D<Length>,
+    pos2: Vector2D<Length>,
+    simulation_box: Option<&SimulationBox>,
+) -> Vector2D<Length> {
+    match simulation_box {
+        Some(simulation_box) => simulation_box.minimum_image(pos1 - pos2),
+        None => pos1 - pos2,
+    }
+}
@@ -0,0 +1,160 @@
+//! Temperature control by coupling to a heat bath.
+//!
+//! All thermostats act on velocities only, scaling them by a common factor, and are
+//! applied for half a time step before and after each integrator step. They need an
+//! integrator that carries velocities, such as `VelocityVerlet`, since `Verlet`
+//! derives its velocities from the positions. A system fully at rest cannot be
+//! heated by rescaling, so velocities should be initialized first.
+//!
+//! The Nosé-Hoover chain is propagated with the Trotter splitting of Martyna,
+//! Tuckerman and Klein. Its thermostat variables are kept dimensionless, in units of
+//! the relaxation time, with masses Q₁ = N_f × k_B × T × τ² and Qⱼ = k_B × T × τ².
+
+use rand::Rng;
+use rand_distr::{ChiSquared, Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Ratio, Time},
+    ratio::ratio,
+};
+
+use crate::physics::time_integration::ParticleState;
+use physics_core::config::{ThermostatConfig, ThermostatKind};
+
+/// Thermostat state, built from its configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Thermostat {
+    config: ThermostatConfig,
+    /// Nosé-Hoover chain velocities ξⱼ × τ
+    chain: Vec<f64>,
+}
+
+impl Thermostat {
+    #[must_use]
+    pub fn new(config: ThermostatConfig) -> Self {
+        let chain_length = match config.kind {
+            ThermostatKind::NoseHooverChain { chain_length, .. } => chain_length.max(1),
+            _ => 0,
+        };
+        Self {
+            config,
+            chain: vec![0.; chain_length],
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ThermostatConfig {
+        &self.config
+    }
+
+    /// Couple `state` to the heat bath for the duration of `time_step`
+    pub fn apply(&mut self, state: &mut ParticleState, time_step: Time, rng: &mut impl Rng) {
+        // Ratio of the current to the target kinetic energy
+        let temperature_ratio =
+            (state.temperature() / self.config.target_temperature).get::<ratio>();
+        if state.is_empty() || !temperature_ratio.is_normal() {
+            return;
+        }
+
+        let scale = match self.config.kind {
+            ThermostatKind::VelocityRescale => (1. / temperature_ratio).sqrt(),
+            ThermostatKind::Berendsen { relaxation_time } => {
+                let coupling = (time_step / relaxation_time).get::<ratio>();
+                (1. + coupling * (1. / temperature_ratio - 1.))
+                    .max(0.)
+                    .sqrt()
+            }
+            ThermostatKind::Bussi { relaxation_time } => {
+                let decay = (-(time_step / relaxation_time).get::<ratio>()).exp();
+                bussi_scale(temperature_ratio, decay, state.degrees_of_freedom(), rng)
+            }
+            ThermostatKind::NoseHooverChain {
+                relaxation_time, ..
+            } => {
+                let step = (time_step / relaxation_time).get::<ratio>();
+                self.nose_hoover_chain_scale(temperature_ratio, step, state.degrees_of_freedom())
+            }
+        };
+
+        let scale = Ratio::new::<ratio>(scale);
+        for vel in &mut state.velocities {
+            *vel = scale * *vel;
+        }
+    }
+
+    /// Discard the chain velocities, e.g. after particles were replaced
+    pub fn reset(&mut self) {
+        self.chain.fill(0.);
+    }
+
+    /// Propagate the chain over the dimensionless time `step`,
+    /// returning the resulting velocity scale factor
+    fn nose_hoover_chain_scale(
+        &mut self,
+        temperature_ratio: f64,
+        step: f64,
+        degrees_of_freedom: usize,
+    ) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let degrees_of_freedom = degrees_of_freedom as f64;
+        let chain = &mut self.chain;
+        let last = chain.len() - 1;
+
+        // Dimensionless forces Gⱼ × τ² on the chain elements
+        let force = |chain: &[f64], j: usize, kinetic_ratio: f64| match j {
+            0 => kinetic_ratio - 1.,
+            1 => degrees_of_freedom * chain[0] * chain[0] - 1.,
+            _ => chain[j - 1] * chain[j - 1] - 1.,
+        };
+        // Velocity update of element j, damped by the next element in the chain
+        let update = |chain: &mut [f64], j: usize, kinetic_ratio: f64| {
+            let damping = if j < last {
+                (-chain[j + 1] * step / 4.).exp()
+            } else {
+                1.
+            };
+            chain[j] *= damping;
+            chain[j] += force(chain, j, kinetic_ratio) * step / 2.;
+            chain[j] *= damping;
+        };
+
+        let mut kinetic_ratio = temperature_ratio;
+        for j in (0..=last).rev() {
+            update(chain, j, kinetic_ratio);
+        }
+
+        let scale = (-chain[0] * step).exp();
+        kinetic_ratio *= scale * scale;
+
+        for j in 0..=last {
+            update(chain, j, kinetic_ratio);
+        }
+
+        scale
+    }
+}
+
+/// Velocity scale factor of stochastic velocity rescaling, which samples the kinetic
+/// energy K′ = α² × K from its canonical distribution relaxed by the factor `decay`:
+/// α² = c + (1 - c) × (R₁² + Σᵢ Rᵢ²) / (N_f × K/K₀) + 2 × R₁ × √(c × (1 - c) / (N_f × K/K₀))
+fn bussi_scale(
+    temperature_ratio: f64,
+    decay: f64,
+    degrees_of_freedom: usize,
+    rng: &mut impl Rng,
+) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let degrees_of_freedom = degrees_of_freedom as f64;
+    let r1: f64 = StandardNormal.sample(rng);
+    // Sum of the squares of the remaining N_f - 1 standard normal samples
+    let sum_squares = if degrees_of_freedom > 1. {
+        ChiSquared::new(degrees_of_freedom - 1.).map_or(0., |distribution| distribution.sample(rng))
+    } else {
+        0.
+    };
+
+    let factor = (1. - decay) / (degrees_of_freedom * temperature_ratio);
+    let scale_squared =
+        decay + factor * (r1 * r1 + sum_squares) + 2. * r1 * (decay * factor).sqrt();
+    scale_squared.max(0.).sqrt()
+}
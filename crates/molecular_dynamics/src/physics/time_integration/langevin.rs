@@ -0,0 +1,135 @@
+//! Langevin dynamics, integrated with the BAOAB splitting of Leimkuhler and Matthews.
+//!
+//! Besides the forces, each particle feels a friction -γ × m × V and a random force
+//! whose strength is set by the fluctuation-dissipation theorem, so that the system
+//! samples the canonical ensemble at the target temperature. A step is split into
+//! half-kicks (B), half-drifts (A) and an exact Ornstein-Uhlenbeck update (O):
+//! Vₖ₊½ = Vₖ + (τ/2) × Gₖ
+//! R′ = Rₖ + (τ/2) × Vₖ₊½
+//! V′ = c × Vₖ₊½ + √((1 - c²) × k_B × T / m) × ξ,  with c = exp(-γ × τ)
+//! Rₖ₊₁ = R′ + (τ/2) × V′
+//! Vₖ₊₁ = V′ + (τ/2) × Gₖ₊₁
+
+use std::io;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution, StandardNormal};
+use uom::si::{
+    f64::{Frequency, Ratio, ThermodynamicTemperature, Time, Velocity},
+    ratio::ratio,
+};
+
+use crate::{
+    physics::time_integration::{
+        ForceField, Integrator, ParticleState, StepReport, decode_state, encode_state,
+    },
+    random::INTEGRATOR_STREAM,
+};
+use physics_core::{constants::thermal_energy, vector::Vector2D};
+
+/// Langevin integrator with its own seeded random number generator
+#[derive(Debug, Clone)]
+pub struct Langevin {
+    friction: Frequency,
+    temperature: ThermodynamicTemperature,
+    rng: ChaCha8Rng,
+}
+
+impl Langevin {
+    /// Initialise for friction coefficient γ and target temperature T.
+    /// Runs with the same seed produce identical trajectories. Draws from the same
+    /// stream as `random::integrator_rng`, independent of the handler's generator.
+    #[must_use]
+    pub fn new(friction: Frequency, temperature: ThermodynamicTemperature, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(INTEGRATOR_STREAM);
+        Self::with_rng(friction, temperature, rng)
+    }
+
+    /// Initialise drawing the random forces from `rng`, e.g. `random::integrator_rng`
+    #[must_use]
+    pub fn with_rng(
+        friction: Frequency,
+        temperature: ThermodynamicTemperature,
+        rng: ChaCha8Rng,
+    ) -> Self {
+        Self {
+            friction,
+            temperature,
+            rng,
+        }
+    }
+
+    #[must_use]
+    pub fn friction(&self) -> Frequency {
+        self.friction
+    }
+
+    #[must_use]
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        self.temperature
+    }
+
+    pub fn set_temperature(&mut self, temperature: ThermodynamicTemperature) {
+        self.temperature = temperature;
+    }
+
+    /// Random unit normal vector ξ
+    fn normal_vector(&mut self) -> Vector2D<Ratio> {
+        Vector2D {
+            x: Ratio::new::<ratio>(StandardNormal.sample(&mut self.rng)),
+            y: Ratio::new::<ratio>(StandardNormal.sample(&mut self.rng)),
+        }
+    }
+}
+
+impl Integrator for Langevin {
+    fn name(&self) -> &'static str {
+        "Langevin"
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        let half_step = time_step / Ratio::new::<ratio>(2.0);
+        let decay = (-(self.friction * time_step).get::<ratio>()).exp();
+        let noise_variance =
+            Ratio::new::<ratio>(1. - decay * decay) * thermal_energy(self.temperature);
+        let decay = Ratio::new::<ratio>(decay);
+
+        for i in 0..state.len() {
+            // B, A
+            state.velocities[i] += half_step * state.accelerations[i];
+            state.positions[i] += half_step * state.velocities[i];
+
+            // O
+            let noise: Velocity = (noise_variance / state.masses[i]).sqrt();
+            let xi = self.normal_vector();
+            state.velocities[i] = decay * state.velocities[i] + noise * xi;
+
+            // A
+            state.positions[i] += half_step * state.velocities[i];
+        }
+
+        // B
+        forces.compute_accelerations(state);
+        for i in 0..state.len() {
+            state.velocities[i] += half_step * state.accelerations[i];
+        }
+
+        Ok(StepReport::fixed(time_step))
+    }
+
+    fn save_state(&self) -> io::Result<Vec<u8>> {
+        encode_state(&(self.temperature, &self.rng))
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> io::Result<()> {
+        (self.temperature, self.rng) = decode_state(state)?;
+        Ok(())
+    }
+}
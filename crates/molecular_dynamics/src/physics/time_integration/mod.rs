@@ -0,0 +1,173 @@
+//! Time integration of the equations of motion.
+//!
+//! An `Integrator` advances a `ParticleState` by one time step, calling back into a
+//! `ForceField` whenever it needs accelerations for new positions. Integrators own
+//! whatever auxiliary per-particle state their scheme requires, so new schemes can
+//! be added outside this crate and plugged into the `SimulationHandler`.
+
+pub mod langevin;
+pub mod naive;
+pub mod runge_kutta;
+pub mod symplectic;
+pub mod verlet;
+
+pub use langevin::Langevin;
+pub use naive::Naive;
+pub use runge_kutta::EmbeddedRungeKutta;
+pub use symplectic::{Stage, SymplecticComposition};
+pub use verlet::{VelocityVerlet, Verlet};
+
+use std::io;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uom::si::f64::{Acceleration, Energy, Length, Mass, ThermodynamicTemperature, Time, Velocity};
+
+use crate::point_mass::Species;
+use physics_core::{constants::temperature_from_thermal_energy, vector::Vector2D};
+
+/// Per-particle arrays advanced by an integrator
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParticleState {
+    pub positions: Vec<Vector2D<Length>>,
+    pub velocities: Vec<Vector2D<Velocity>>,
+    pub accelerations: Vec<Vector2D<Acceleration>>,
+    pub masses: Vec<Mass>,
+    pub species: Vec<Species>,
+}
+
+impl ParticleState {
+    /// Initialise a state of `len` particles of species 0 at rest in the origin
+    #[must_use]
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            positions: vec![Vector2D::<Length>::zero(); len],
+            velocities: vec![Vector2D::<Velocity>::zero(); len],
+            accelerations: vec![Vector2D::<Acceleration>::zero(); len],
+            masses: vec![Mass::default(); len],
+            species: vec![0; len],
+        }
+    }
+
+    /// Number of particles
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Total kinetic energy: Σ m × V² / 2
+    #[must_use]
+    pub fn kinetic_energy(&self) -> Energy {
+        self.velocities
+            .iter()
+            .zip(&self.masses)
+            .map(|(vel, mass)| *mass * (vel.x * vel.x + vel.y * vel.y) / 2.)
+            .sum()
+    }
+
+    /// Number of degrees of freedom: 2 per particle
+    #[must_use]
+    pub fn degrees_of_freedom(&self) -> usize {
+        2 * self.len()
+    }
+
+    /// Instantaneous temperature by equipartition: 2 × K = N_f × k_B × T
+    #[must_use]
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        #[allow(clippy::cast_precision_loss)]
+        let degrees_of_freedom = self.degrees_of_freedom().max(1) as f64;
+        temperature_from_thermal_energy(2. * self.kinetic_energy() / degrees_of_freedom)
+    }
+}
+
+/// Source of accelerations for a given state
+pub trait ForceField {
+    /// Overwrite `state.accelerations` with those resulting from `state.positions`
+    fn compute_accelerations(&mut self, state: &mut ParticleState);
+}
+
+/// Outcome of a single integrator step
+#[derive(Debug, Clone, Copy)]
+pub struct StepReport {
+    /// Time actually advanced
+    pub time_step: Time,
+    /// Number of trial steps rejected before this one was accepted
+    pub rejected: usize,
+}
+
+impl StepReport {
+    /// Report for a fixed step, accepted as-is
+    #[must_use]
+    pub fn fixed(time_step: Time) -> Self {
+        Self {
+            time_step,
+            rejected: 0,
+        }
+    }
+}
+
+/// A time integration scheme
+pub trait Integrator {
+    /// Name of the scheme, stored in checkpoints to check that they are resumed with
+    /// the same one. Defaults to the type name.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Advance `state` by one time step.
+    /// Adaptive schemes may take a different step than requested; the report says which.
+    ///
+    /// On entry, `state.accelerations` correspond to `state.positions`,
+    /// and implementations must leave them that way on return.
+    ///
+    /// # Errors
+    /// Returns an error if no acceptable step could be taken, e.g. because an adaptive
+    /// step size shrank to nothing.
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport>;
+
+    /// The time step the next call to `step` attempts for a requested `time_step`.
+    /// Adaptive schemes attempt the step size they adapted to, others the requested one.
+    fn planned_time_step(&self, time_step: Time) -> Time {
+        time_step
+    }
+
+    /// Discard any auxiliary state, e.g. after particles were moved externally
+    fn reset(&mut self) {}
+
+    /// Encode the auxiliary state carried between steps, to be stored in a checkpoint.
+    /// Integrators without such state store nothing.
+    ///
+    /// # Errors
+    /// Returns an error if the state cannot be encoded.
+    fn save_state(&self) -> io::Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    /// Restore the auxiliary state encoded by `save_state`
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if `state` cannot be decoded.
+    fn load_state(&mut self, state: &[u8]) -> io::Result<()> {
+        let _ = state;
+        Ok(())
+    }
+}
+
+/// Encode integrator state for `Integrator::save_state`
+pub(crate) fn encode_state(state: &impl Serialize) -> io::Result<Vec<u8>> {
+    bincode::serialize(state).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Decode integrator state for `Integrator::load_state`
+pub(crate) fn decode_state<T: DeserializeOwned>(state: &[u8]) -> io::Result<T> {
+    bincode::deserialize(state).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
@@ -0,0 +1,31 @@
+use std::io;
+
+use uom::si::f64::Time;
+
+use crate::physics::time_integration::{ForceField, Integrator, ParticleState, StepReport};
+
+/// Naive update method (Rₖ₊₁ = Rₖ + τ × Vₖ) and equiv. for velocity
+#[derive(Debug, Clone, Default)]
+pub struct Naive;
+
+impl Integrator for Naive {
+    fn name(&self) -> &'static str {
+        "Naive"
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        for i in 0..state.len() {
+            state.positions[i] += time_step * state.velocities[i];
+            state.velocities[i] += time_step * state.accelerations[i];
+        }
+
+        forces.compute_accelerations(state);
+
+        Ok(StepReport::fixed(time_step))
+    }
+}
@@ -0,0 +1,344 @@
+//! Adaptive time stepping with embedded Runge-Kutta pairs.
+//!
+//! The equations of motion are integrated as the first-order system
+//! d/dt (R, V) = (V, G(R)). Each step yields two solutions of different order,
+//! whose difference estimates the local error. Steps with an error above the
+//! tolerance are rejected and retried with a smaller time step, and the step size
+//! for the next step is adapted to the error of the last one. A step that cannot meet
+//! the tolerance before shrinking to a negligible size fails with an error.
+
+use std::io;
+
+use uom::si::{
+    f64::{Acceleration, Length, Time, Velocity},
+    time::second,
+};
+
+use crate::physics::time_integration::{
+    ForceField, Integrator, ParticleState, StepReport, decode_state, encode_state,
+};
+use physics_core::{config::AdaptiveTolerance, vector::Vector2D};
+
+/// Limits to the change in step size between consecutive steps
+const SAFETY_FACTOR: f64 = 0.9;
+const MIN_SCALE: f64 = 0.2;
+const MAX_SCALE: f64 = 5.0;
+
+/// Without a `min_time_step`, steps are not made smaller than this fraction of the
+/// requested time step, as they could no longer advance the time
+const MIN_STEP_FRACTION: f64 = 1e-12;
+
+/// Coefficients of an embedded Runge-Kutta pair
+struct ButcherTableau {
+    name: &'static str,
+    /// Stage coefficients, row `s` has `s` entries
+    a: &'static [&'static [f64]],
+    /// Weights of the solution used to advance
+    b: &'static [f64],
+    /// Weights of the embedded solution used for error estimation
+    b_hat: &'static [f64],
+    /// Lowest order of the two solutions
+    order: i32,
+}
+
+impl ButcherTableau {
+    /// First-same-as-last: the final stage is evaluated at the new solution, i.e. the
+    /// last row of `a` equals `b`, whose weight of the final stage itself is zero
+    fn is_fsal(&self) -> bool {
+        self.a.last().is_some_and(|row| {
+            if row.len() >= self.b.len() {
+                return false;
+            }
+            let (weights, rest) = self.b.split_at(row.len());
+            *row == weights && rest.iter().all(|&weight| weight == 0.)
+        })
+    }
+}
+
+const FEHLBERG: ButcherTableau = ButcherTableau {
+    name: "Fehlberg",
+    a: &[
+        &[],
+        &[1. / 4.],
+        &[3. / 32., 9. / 32.],
+        &[1932. / 2197., -7200. / 2197., 7296. / 2197.],
+        &[439. / 216., -8., 3680. / 513., -845. / 4104.],
+        &[-8. / 27., 2., -3544. / 2565., 1859. / 4104., -11. / 40.],
+    ],
+    b: &[25. / 216., 0., 1408. / 2565., 2197. / 4104., -1. / 5., 0.],
+    b_hat: &[
+        16. / 135.,
+        0.,
+        6656. / 12825.,
+        28561. / 56430.,
+        -9. / 50.,
+        2. / 55.,
+    ],
+    order: 4,
+};
+
+const DORMAND_PRINCE: ButcherTableau = ButcherTableau {
+    name: "DormandPrince",
+    a: &[
+        &[],
+        &[1. / 5.],
+        &[3. / 40., 9. / 40.],
+        &[44. / 45., -56. / 15., 32. / 9.],
+        &[
+            19372. / 6561.,
+            -25360. / 2187.,
+            64448. / 6561.,
+            -212. / 729.,
+        ],
+        &[
+            9017. / 3168.,
+            -355. / 33.,
+            46732. / 5247.,
+            49. / 176.,
+            -5103. / 18656.,
+        ],
+        &[
+            35. / 384.,
+            0.,
+            500. / 1113.,
+            125. / 192.,
+            -2187. / 6784.,
+            11. / 84.,
+        ],
+    ],
+    b: &[
+        35. / 384.,
+        0.,
+        500. / 1113.,
+        125. / 192.,
+        -2187. / 6784.,
+        11. / 84.,
+        0.,
+    ],
+    b_hat: &[
+        5179. / 57600.,
+        0.,
+        7571. / 16695.,
+        393. / 640.,
+        -92097. / 339_200.,
+        187. / 2100.,
+        1. / 40.,
+    ],
+    order: 4,
+};
+
+/// Adaptive integrator using an embedded Runge-Kutta pair.
+///
+/// The time step passed to `step` is only used as the initial guess;
+/// afterwards the step size is chosen to keep the error within tolerance.
+pub struct EmbeddedRungeKutta {
+    tableau: &'static ButcherTableau,
+    tolerance: AdaptiveTolerance,
+    next_time_step: Option<Time>,
+    /// Stage derivatives of position and velocity
+    k_pos: Vec<Vec<Vector2D<Velocity>>>,
+    k_vel: Vec<Vec<Vector2D<Acceleration>>>,
+    stage_state: ParticleState,
+}
+
+impl EmbeddedRungeKutta {
+    fn new(tableau: &'static ButcherTableau, tolerance: AdaptiveTolerance) -> Self {
+        Self {
+            tableau,
+            tolerance,
+            next_time_step: None,
+            k_pos: Vec::new(),
+            k_vel: Vec::new(),
+            stage_state: ParticleState::default(),
+        }
+    }
+
+    /// Runge-Kutta-Fehlberg 4(5) pair, advancing with the 4th order solution
+    #[must_use]
+    pub fn fehlberg(tolerance: AdaptiveTolerance) -> Self {
+        Self::new(&FEHLBERG, tolerance)
+    }
+
+    /// Dormand-Prince 5(4) pair, advancing with the 5th order solution
+    #[must_use]
+    pub fn dormand_prince(tolerance: AdaptiveTolerance) -> Self {
+        Self::new(&DORMAND_PRINCE, tolerance)
+    }
+
+    fn clamp_time_step(&self, time_step: Time) -> Time {
+        let mut time_step = time_step;
+        if let Some(max) = self.tolerance.max_time_step {
+            time_step = time_step.min(max);
+        }
+        if let Some(min) = self.tolerance.min_time_step {
+            time_step = time_step.max(min);
+        }
+        time_step
+    }
+
+    /// Evaluate all stages for a step of size `h`, starting from `state`
+    fn compute_stages(&mut self, state: &ParticleState, forces: &mut dyn ForceField, h: Time) {
+        let stages = self.tableau.a.len();
+        self.k_pos.resize(stages, Vec::new());
+        self.k_vel.resize(stages, Vec::new());
+
+        // The first stage is evaluated at the current state, for which accelerations are known
+        self.k_pos[0].clone_from(&state.velocities);
+        self.k_vel[0].clone_from(&state.accelerations);
+
+        for (s, row) in self.tableau.a.iter().enumerate().skip(1) {
+            self.stage_state.clone_from(state);
+            for (j, &a) in row.iter().enumerate() {
+                for i in 0..state.len() {
+                    self.stage_state.positions[i] += (h * a) * self.k_pos[j][i];
+                    self.stage_state.velocities[i] += (h * a) * self.k_vel[j][i];
+                }
+            }
+            forces.compute_accelerations(&mut self.stage_state);
+
+            self.k_pos[s].clone_from(&self.stage_state.velocities);
+            self.k_vel[s].clone_from(&self.stage_state.accelerations);
+        }
+    }
+
+    /// Error of the stages computed for step size `h`, relative to the tolerance.
+    /// The relative tolerance applies to the larger of the values before and after the
+    /// step, so that it also holds for values starting at zero, e.g. particles at rest.
+    fn scaled_error(&self, state: &ParticleState, h: Time) -> f64 {
+        let n = state.len();
+        let mut pos_error = 0.;
+        let mut vel_error = 0.;
+        let mut pos_scale = 0.;
+        let mut vel_scale = 0.;
+
+        for i in 0..n {
+            let mut delta_pos = Vector2D::<Length>::zero();
+            let mut delta_vel = Vector2D::<Velocity>::zero();
+            let mut new_pos = state.positions[i];
+            let mut new_vel = state.velocities[i];
+            for (s, (b, b_hat)) in self.tableau.b.iter().zip(self.tableau.b_hat).enumerate() {
+                delta_pos += (h * (b - b_hat)) * self.k_pos[s][i];
+                delta_vel += (h * (b - b_hat)) * self.k_vel[s][i];
+                new_pos += (h * *b) * self.k_pos[s][i];
+                new_vel += (h * *b) * self.k_vel[s][i];
+            }
+            pos_error += delta_pos.mag().value.powi(2);
+            vel_error += delta_vel.mag().value.powi(2);
+            pos_scale += state.positions[i]
+                .mag()
+                .value
+                .max(new_pos.mag().value)
+                .powi(2);
+            vel_scale += state.velocities[i]
+                .mag()
+                .value
+                .max(new_vel.mag().value)
+                .powi(2);
+        }
+
+        // root-mean-square over all particles
+        #[allow(clippy::cast_precision_loss)]
+        let n = n.max(1) as f64;
+        let ratio = |error: f64, scale: f64, absolute: f64| {
+            let error = (error / n).sqrt();
+            let allowed = absolute + self.tolerance.relative * (scale / n).sqrt();
+            if error == 0. { 0. } else { error / allowed }
+        };
+
+        ratio(pos_error, pos_scale, self.tolerance.absolute_position.value).max(ratio(
+            vel_error,
+            vel_scale,
+            self.tolerance.absolute_velocity.value,
+        ))
+    }
+
+    /// Factor to scale the step size by, for a given scaled error.
+    /// A non-finite error, e.g. from particles on top of each other, shrinks it the most.
+    fn step_scale(&self, error: f64) -> f64 {
+        if error == 0. {
+            return MAX_SCALE;
+        }
+        if !error.is_finite() {
+            return MIN_SCALE;
+        }
+        (SAFETY_FACTOR * error.powf(-1. / f64::from(self.tableau.order + 1)))
+            .clamp(MIN_SCALE, MAX_SCALE)
+    }
+}
+
+impl Integrator for EmbeddedRungeKutta {
+    fn name(&self) -> &'static str {
+        self.tableau.name
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        let mut h = self.planned_time_step(time_step);
+        let min_step = (self.tolerance.min_time_step)
+            .unwrap_or(time_step * MIN_STEP_FRACTION)
+            .abs();
+        let mut rejected = 0;
+
+        loop {
+            self.compute_stages(state, forces, h);
+            let error = self.scaled_error(state, h);
+            let at_min_step = self.tolerance.min_time_step.is_some_and(|min| h <= min);
+
+            if error <= 1. || (at_min_step && error.is_finite()) {
+                self.next_time_step = Some(self.clamp_time_step(h * self.step_scale(error)));
+                break;
+            }
+
+            h = self.clamp_time_step(h * self.step_scale(error));
+            rejected += 1;
+            if at_min_step || h.is_nan() || h.abs() < min_step {
+                return Err(io::Error::other(format!(
+                    "adaptive time step shrank to {:e} s without meeting the error tolerance",
+                    h.get::<second>()
+                )));
+            }
+        }
+
+        // advance with the main solution
+        for i in 0..state.len() {
+            for (s, &b) in self.tableau.b.iter().enumerate() {
+                state.positions[i] += (h * b) * self.k_pos[s][i];
+                state.velocities[i] += (h * b) * self.k_vel[s][i];
+            }
+        }
+
+        if self.tableau.is_fsal() {
+            state
+                .accelerations
+                .clone_from(&self.k_vel[self.tableau.a.len() - 1]);
+        } else {
+            forces.compute_accelerations(state);
+        }
+
+        Ok(StepReport {
+            time_step: h,
+            rejected,
+        })
+    }
+
+    fn planned_time_step(&self, time_step: Time) -> Time {
+        self.clamp_time_step(self.next_time_step.unwrap_or(time_step))
+    }
+
+    fn reset(&mut self) {
+        self.next_time_step = None;
+    }
+
+    fn save_state(&self) -> io::Result<Vec<u8>> {
+        encode_state(&self.next_time_step)
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> io::Result<()> {
+        self.next_time_step = decode_state(state)?;
+        Ok(())
+    }
+}
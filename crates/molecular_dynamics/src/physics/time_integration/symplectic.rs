@@ -0,0 +1,162 @@
+//! Higher-order symplectic integrators, composed of drifts and kicks.
+//!
+//! Each scheme is a fixed sequence of position updates (drifts, R += c·τ × V) and
+//! velocity updates (kicks, V += d·τ × G). Forces are only recomputed before a kick
+//! that follows a drift, so a scheme costs one force evaluation per drift.
+//! The built-in schemes start and end with a kick, so the forces of the last kick of a
+//! step are those of the first kick of the next one. A scheme ending with a drift
+//! costs one more evaluation, to leave the accelerations current for the next step.
+
+use std::io;
+
+use uom::si::f64::Time;
+
+use crate::physics::time_integration::{ForceField, Integrator, ParticleState, StepReport};
+
+/// A single sub-step, as a fraction of the full time step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    /// Position update: R += c·τ × V
+    Drift(f64),
+    /// Velocity update: V += d·τ × G
+    Kick(f64),
+}
+
+/// Symplectic integrator built from a sequence of drifts and kicks
+#[derive(Debug, Clone)]
+pub struct SymplecticComposition {
+    name: &'static str,
+    stages: Vec<Stage>,
+}
+
+impl SymplecticComposition {
+    /// Compose a scheme from arbitrary stages, under a name identifying it in checkpoints.
+    /// For consistency, the drift and kick coefficients should each sum to 1.
+    #[must_use]
+    pub fn new(name: &'static str, stages: Vec<Stage>) -> Self {
+        Self { name, stages }
+    }
+
+    /// Chain Velocity Verlet steps of the given fractions of the time step,
+    /// merging the touching half-kicks of consecutive steps
+    fn from_velocity_verlet_weights(name: &'static str, weights: &[f64]) -> Self {
+        let mut stages = Vec::with_capacity(2 * weights.len() + 1);
+        let mut pending_kick = 0.;
+        for &weight in weights {
+            stages.push(Stage::Kick(pending_kick + weight / 2.));
+            stages.push(Stage::Drift(weight));
+            pending_kick = weight / 2.;
+        }
+        stages.push(Stage::Kick(pending_kick));
+        Self { name, stages }
+    }
+
+    /// Forest-Ruth 4th order scheme, velocity version (3 force evaluations).
+    /// Found independently by Yoshida, it is the same scheme as `yoshida4`.
+    #[must_use]
+    pub fn forest_ruth() -> Self {
+        let theta = 1. / (2. - 2_f64.cbrt());
+        Self::new(
+            "ForestRuth",
+            vec![
+                Stage::Kick(theta / 2.),
+                Stage::Drift(theta),
+                Stage::Kick((1. - theta) / 2.),
+                Stage::Drift(1. - 2. * theta),
+                Stage::Kick((1. - theta) / 2.),
+                Stage::Drift(theta),
+                Stage::Kick(theta / 2.),
+            ],
+        )
+    }
+
+    /// Yoshida 4th order scheme: triple jump of Velocity Verlet steps (3 force evaluations)
+    #[must_use]
+    pub fn yoshida4() -> Self {
+        let w1 = 1. / (2. - 2_f64.cbrt());
+        let w0 = 1. - 2. * w1;
+        Self::from_velocity_verlet_weights("Yoshida4", &[w1, w0, w1])
+    }
+
+    /// Yoshida 6th order scheme, solution A (7 force evaluations)
+    #[must_use]
+    pub fn yoshida6() -> Self {
+        let w1 = -1.177_679_984_178_87;
+        let w2 = 0.235_573_213_359_357;
+        let w3 = 0.784_513_610_477_560;
+        let w0 = 1. - 2. * (w1 + w2 + w3);
+        Self::from_velocity_verlet_weights("Yoshida6", &[w3, w2, w1, w0, w1, w2, w3])
+    }
+
+    /// Extended Forest-Ruth-like 4th order scheme of Omelyan, Mryglod and Folk, in its
+    /// velocity version (VEFRL), with a much smaller error constant than Forest-Ruth
+    /// (4 force evaluations)
+    #[must_use]
+    pub fn pefrl() -> Self {
+        let xi = 0.164_498_651_557_576;
+        let lambda = -0.020_943_339_103_989_89;
+        let chi = 1.235_692_651_138_917;
+        Self::new(
+            "Pefrl",
+            vec![
+                Stage::Kick(xi),
+                Stage::Drift((1. - 2. * lambda) / 2.),
+                Stage::Kick(chi),
+                Stage::Drift(lambda),
+                Stage::Kick(1. - 2. * (chi + xi)),
+                Stage::Drift(lambda),
+                Stage::Kick(chi),
+                Stage::Drift((1. - 2. * lambda) / 2.),
+                Stage::Kick(xi),
+            ],
+        )
+    }
+
+    #[must_use]
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+}
+
+impl Integrator for SymplecticComposition {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        let mut accelerations_current = true;
+
+        for stage in &self.stages {
+            match *stage {
+                Stage::Drift(c) => {
+                    let sub_step = time_step * c;
+                    for i in 0..state.len() {
+                        state.positions[i] += sub_step * state.velocities[i];
+                    }
+                    accelerations_current = false;
+                }
+                Stage::Kick(d) => {
+                    if !accelerations_current {
+                        forces.compute_accelerations(state);
+                        accelerations_current = true;
+                    }
+                    let sub_step = time_step * d;
+                    for i in 0..state.len() {
+                        state.velocities[i] += sub_step * state.accelerations[i];
+                    }
+                }
+            }
+        }
+
+        if !accelerations_current {
+            forces.compute_accelerations(state);
+        }
+
+        Ok(StepReport::fixed(time_step))
+    }
+}
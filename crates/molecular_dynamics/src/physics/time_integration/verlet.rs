@@ -0,0 +1,108 @@
+use std::io;
+
+use uom::si::{
+    f64::{Length, Ratio, Time},
+    ratio::ratio,
+};
+
+use crate::physics::time_integration::{
+    ForceField, Integrator, ParticleState, StepReport, decode_state, encode_state,
+};
+use physics_core::vector::Vector2D;
+
+/// Base Verlet update method: Rₖ₊₁ = 2Rₖ - Rₖ₋₁ + τ² × Gₖ
+///
+/// Rather than previous positions, the last displacements Rₖ - Rₖ₋₁ are kept,
+/// which stay valid when positions are wrapped around a periodic box.
+#[derive(Debug, Clone, Default)]
+pub struct Verlet {
+    displacements: Vec<Vector2D<Length>>,
+}
+
+impl Integrator for Verlet {
+    fn name(&self) -> &'static str {
+        "Verlet"
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        // approximation for initial R₀ - R₋₁ ~ τ × V₀ - (τ² × G₀) / 2
+        if self.displacements.len() != state.len() {
+            self.displacements = (0..state.len())
+                .map(|i| {
+                    time_step * state.velocities[i]
+                        - time_step * (time_step * state.accelerations[i]) / 2.
+                })
+                .collect();
+        }
+
+        for i in 0..state.len() {
+            let last_displacement = self.displacements[i];
+            let displacement = last_displacement + time_step * (time_step * state.accelerations[i]);
+
+            state.positions[i] += displacement;
+            // central difference (Rₖ₊₁ - Rₖ₋₁) / 2τ, i.e. lagging one step behind
+            state.velocities[i] =
+                (last_displacement + displacement) / (Ratio::new::<ratio>(2.) * time_step);
+
+            self.displacements[i] = displacement;
+        }
+
+        forces.compute_accelerations(state);
+
+        Ok(StepReport::fixed(time_step))
+    }
+
+    fn reset(&mut self) {
+        self.displacements.clear();
+    }
+
+    fn save_state(&self) -> io::Result<Vec<u8>> {
+        encode_state(&self.displacements)
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> io::Result<()> {
+        self.displacements = decode_state(state)?;
+        Ok(())
+    }
+}
+
+/// Velocity Verlet update method, with forces recomputed between the half-kicks:
+/// Vₖ₊½ = Vₖ + (τ/2) × Gₖ
+/// Rₖ₊₁ = Rₖ + τ × Vₖ₊½
+/// Vₖ₊₁ = Vₖ₊½ + (τ/2) × Gₖ₊₁
+#[derive(Debug, Clone, Default)]
+pub struct VelocityVerlet;
+
+impl Integrator for VelocityVerlet {
+    fn name(&self) -> &'static str {
+        "VelocityVerlet"
+    }
+
+    fn step(
+        &mut self,
+        state: &mut ParticleState,
+        forces: &mut dyn ForceField,
+        time_step: Time,
+    ) -> io::Result<StepReport> {
+        let half_step = time_step / Ratio::new::<ratio>(2.0);
+
+        // half-kick and drift
+        for i in 0..state.len() {
+            state.velocities[i] += half_step * state.accelerations[i];
+            state.positions[i] += time_step * state.velocities[i];
+        }
+
+        // force recompute and half-kick
+        forces.compute_accelerations(state);
+        for i in 0..state.len() {
+            state.velocities[i] += half_step * state.accelerations[i];
+        }
+
+        Ok(StepReport::fixed(time_step))
+    }
+}
@@ -1,12 +1,21 @@
-use macroquad::prelude::rand;
+use std::f64::consts::TAU;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use uom::si::{
-    f64::{Acceleration, Length, Mass, Velocity},
+    f64::{Acceleration, ArealNumberDensity, Length, Mass, Velocity},
     mass::kilogram,
+    ratio::ratio,
 };
 
-use crate::point_mass::PointMass;
+use crate::{
+    physics::simulation_box::{SimulationBox, separation},
+    point_mass::{PointMass, Species, velocities::MaxwellBoltzmann},
+};
 use physics_core::vector::Vector2D;
-use visualization::simulation::config::SimulationConfig;
+
+/// Attempts at placing a single point in random sequential addition before giving up
+const RANDOM_SEQUENTIAL_ATTEMPTS: usize = 1000;
 
 /// A cluster of `PointMass`es
 pub struct Cluster {
@@ -14,7 +23,19 @@ pub struct Cluster {
     pub points: Vec<Box<PointMass>>,
 }
 
-#[derive(Debug)]
+/// A region of the plane to place points in
+pub trait Region {
+    /// Whether `pos` lies within the region, boundary included
+    fn contains(&self, pos: Vector2D<Length>) -> bool;
+
+    /// The smallest rectangle enclosing the region, with `x1 <= x2` and `y1 <= y2`
+    fn bounding_rectangle(&self) -> RectangularBounds;
+
+    /// A position drawn uniformly at random from the region
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2D<Length>;
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct RectangularBounds {
     pub x1: Length,
     pub x2: Length,
@@ -22,49 +43,393 @@ pub struct RectangularBounds {
     pub y2: Length,
 }
 
+impl Region for RectangularBounds {
+    fn contains(&self, pos: Vector2D<Length>) -> bool {
+        let RectangularBounds { x1, x2, y1, y2 } = self.bounding_rectangle();
+        (x1..=x2).contains(&pos.x) && (y1..=y2).contains(&pos.y)
+    }
+
+    fn bounding_rectangle(&self) -> RectangularBounds {
+        RectangularBounds {
+            x1: self.x1.min(self.x2),
+            x2: self.x1.max(self.x2),
+            y1: self.y1.min(self.y2),
+            y2: self.y1.max(self.y2),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2D<Length> {
+        Vector2D {
+            x: self.x1 + rng.random::<f64>() * (self.x2 - self.x1),
+            y: self.y1 + rng.random::<f64>() * (self.y2 - self.y1),
+        }
+    }
+}
+
+/// An annulus around `center`, or a disk if `inner_radius` is zero
+#[derive(Debug, Clone, Copy)]
+pub struct CircularBounds {
+    pub center: Vector2D<Length>,
+    pub inner_radius: Length,
+    pub outer_radius: Length,
+}
+
+impl CircularBounds {
+    /// The disk of `radius` around `center`
+    #[must_use]
+    pub fn disk(center: Vector2D<Length>, radius: Length) -> Self {
+        Self {
+            center,
+            inner_radius: Length::default(),
+            outer_radius: radius,
+        }
+    }
+}
+
+impl Region for CircularBounds {
+    fn contains(&self, pos: Vector2D<Length>) -> bool {
+        (self.inner_radius..=self.outer_radius).contains(&(pos - self.center).mag())
+    }
+
+    fn bounding_rectangle(&self) -> RectangularBounds {
+        RectangularBounds {
+            x1: self.center.x - self.outer_radius,
+            x2: self.center.x + self.outer_radius,
+            y1: self.center.y - self.outer_radius,
+            y2: self.center.y + self.outer_radius,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2D<Length> {
+        // The enclosed area, not the radius, grows uniformly
+        let (inner, outer) = (self.inner_radius, self.outer_radius);
+        let radius: Length =
+            (inner * inner + rng.random::<f64>() * (outer * outer - inner * inner)).sqrt();
+        let angle = TAU * rng.random::<f64>();
+        self.center
+            + Vector2D {
+                x: radius * angle.cos(),
+                y: radius * angle.sin(),
+            }
+    }
+}
+
+/// Two-dimensional lattices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lattice {
+    /// Square lattice, with neighbours a lattice constant apart
+    Square,
+    /// Hexagonal (triangular) lattice, with neighbours a lattice constant apart.
+    /// The densest packing of disks.
+    Hexagonal,
+    /// Honeycomb lattice, as in graphene: a hexagonal lattice with two sites per cell,
+    /// so that neighbours are a lattice constant / √3 apart
+    Honeycomb,
+}
+
+impl Lattice {
+    /// Area per site, in squared lattice constants
+    fn area_per_site(self) -> f64 {
+        match self {
+            Self::Square => 1.,
+            Self::Hexagonal => 3_f64.sqrt() / 2.,
+            Self::Honeycomb => 3_f64.sqrt() / 4.,
+        }
+    }
+
+    /// Width and height of the rectangular unit cell, in lattice constants, and the
+    /// fractional coordinates of its sites
+    fn rectangular_cell(self) -> (f64, f64, &'static [(f64, f64)]) {
+        match self {
+            Self::Square => (1., 1., &[(0., 0.)]),
+            Self::Hexagonal => (1., 3_f64.sqrt(), &[(0., 0.), (0.5, 0.5)]),
+            Self::Honeycomb => (
+                1.,
+                3_f64.sqrt(),
+                &[(0., 0.), (0.5, 1. / 6.), (0.5, 0.5), (0., 2. / 3.)],
+            ),
+        }
+    }
+
+    /// Lattice constant at which the lattice has the given number of sites per area
+    #[must_use]
+    pub fn lattice_constant(self, density: ArealNumberDensity) -> Length {
+        (density * self.area_per_site()).recip().sqrt()
+    }
+}
+
 impl Cluster {
+    /// Points at rest, placed uniformly at random within `position_bounds`.
+    /// Positions are drawn from `rng`, e.g. one `random::initialization_rng` shared by
+    /// all clusters of a setup, so they are reproducible if it has a seed.
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn new(
-        config: &SimulationConfig,
         position_bounds: &RectangularBounds,
         no_of_points: u32,
         mass_of_points: Mass,
+        rng: &mut impl Rng,
+    ) -> Self {
+        Self::uniform_random(position_bounds, no_of_points as usize, mass_of_points, rng)
+    }
+
+    /// Points at rest, placed uniformly at random within `region`,
+    /// with positions drawn from `rng`
+    #[must_use]
+    pub fn uniform_random(
+        region: &impl Region,
+        count: usize,
+        mass: Mass,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let points = (0..count)
+            .map(|_| Box::new(at_rest(region.sample(rng), mass)))
+            .collect();
+
+        Self { points }
+    }
+
+    /// Points at rest on the sites of `lattice` that lie within `region`.
+    ///
+    /// The lattice is centred in the bounding rectangle of the region, keeping at least
+    /// a quarter of a unit cell to its edges. Filling a periodic box whose sides are
+    /// multiples of the rectangular unit cell (a × a for the square lattice, a × √3 a
+    /// otherwise) thus gives a lattice that continues across the boundaries.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn lattice(
+        region: &impl Region,
+        lattice: Lattice,
+        lattice_constant: Length,
+        mass: Mass,
     ) -> Self {
-        let mut points: Vec<Box<PointMass>> = Vec::new();
-        for _i in 0..no_of_points {
-            points.push(Box::new(PointMass::new(
-                Vector2D {
-                    x: config.length_unit.new(f64::from(rand::gen_range(
-                        config.length_unit.get(position_bounds.x1) as f32,
-                        config.length_unit.get(position_bounds.x2) as f32,
-                    ))),
-                    y: config.length_unit.new(f64::from(rand::gen_range(
-                        config.length_unit.get(position_bounds.y1) as f32,
-                        config.length_unit.get(position_bounds.y2) as f32,
-                    ))),
-                },
-                // units don't matter here, as long as we're initialising with zero-vector
-                Vector2D::<Velocity>::zero(),
-                Vector2D::<Acceleration>::zero(),
-                mass_of_points,
-                config.time_step,
-            )));
+        let (width, height, sites) = lattice.rectangular_cell();
+        let cell = Vector2D {
+            x: width * lattice_constant,
+            y: height * lattice_constant,
+        };
+        let RectangularBounds { x1, x2, y1, y2 } = region.bounding_rectangle();
+        let columns = ((x2 - x1) / cell.x).get::<ratio>();
+        let rows = ((y2 - y1) / cell.y).get::<ratio>();
+        let origin = Vector2D {
+            x: x1 + centring_margin(columns) * cell.x,
+            y: y1 + centring_margin(rows) * cell.y,
+        };
+
+        // One extra cell on either side, to catch sites in partially covered cells
+        let mut points = Vec::new();
+        for row in -1..=rows.ceil() as i64 {
+            for column in -1..=columns.ceil() as i64 {
+                for &(s_x, s_y) in sites {
+                    let pos = Vector2D {
+                        x: origin.x + (column as f64 + s_x) * cell.x,
+                        y: origin.y + (row as f64 + s_y) * cell.y,
+                    };
+                    if region.contains(pos) {
+                        points.push(Box::new(at_rest(pos, mass)));
+                    }
+                }
+            }
         }
 
         Self { points }
     }
 
+    /// `count` points at rest, placed one by one uniformly at random within `region`,
+    /// rejecting positions closer than `min_separation` to any point placed before
+    /// (random sequential addition). With a `simulation_box`, distances are taken
+    /// between minimum images.
+    ///
+    /// Returns `None` if a point cannot be placed in `RANDOM_SEQUENTIAL_ATTEMPTS`
+    /// attempts. Random sequential addition jams once disks of diameter
+    /// `min_separation` around the points cover about 55% of the region.
     #[must_use]
-    pub fn center_of_mass(&self) -> Vector2D<Length> {
-        // Single pass-over to calculate weighted sum and total mass
-        let (weighted_sum, total_mass) = self.points.iter().fold(
-            (
-                Vector2D::<Length>::zero() * Mass::new::<kilogram>(0.0),
-                Mass::new::<kilogram>(0.0),
-            ),
-            |(com, total_mass), obj| (com + obj.pos() * obj.mass(), total_mass + obj.mass()),
+    pub fn random_sequential_addition(
+        region: &impl Region,
+        count: usize,
+        min_separation: Length,
+        mass: Mass,
+        simulation_box: Option<&SimulationBox>,
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        if min_separation <= Length::default() {
+            return Some(Self::uniform_random(region, count, mass, rng));
+        }
+
+        let mut grid = SeparationGrid::new(
+            &region.bounding_rectangle(),
+            count,
+            min_separation,
+            simulation_box,
         );
-        weighted_sum / total_mass
+        let mut positions: Vec<Vector2D<Length>> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let pos = (0..RANDOM_SEQUENTIAL_ATTEMPTS)
+                .map(|_| region.sample(rng))
+                .find(|&pos| grid.is_free(&positions, pos))?;
+            grid.insert(positions.len(), pos);
+            positions.push(pos);
+        }
+
+        Some(Self {
+            points: positions
+                .into_iter()
+                .map(|pos| Box::new(at_rest(pos, mass)))
+                .collect(),
+        })
+    }
+
+    /// Returns the cluster with all points of the given species
+    #[must_use]
+    pub fn with_species(mut self, species: Species) -> Self {
+        for point in &mut self.points {
+            point.species = species;
+        }
+        self
+    }
+
+    /// Overwrite the velocities of the points with draws from `distribution`
+    pub fn thermalize(&mut self, distribution: &MaxwellBoltzmann, rng: &mut impl Rng) {
+        distribution.apply(self.points.iter_mut().map(|point| &mut **point), rng);
+    }
+
+    #[must_use]
+    pub fn center_of_mass(&self) -> Vector2D<Length> {
+        center_of_mass(self.points.iter().map(|obj| (obj.pos(), obj.mass())))
+    }
+}
+
+/// Mass-weighted average of the given positions
+pub fn center_of_mass(
+    points: impl IntoIterator<Item = (Vector2D<Length>, Mass)>,
+) -> Vector2D<Length> {
+    // Single pass-over to calculate weighted sum and total mass
+    let (weighted_sum, total_mass) = points.into_iter().fold(
+        (
+            Vector2D::<Length>::zero() * Mass::new::<kilogram>(0.0),
+            Mass::new::<kilogram>(0.0),
+        ),
+        |(com, total_mass), (pos, mass)| (com + pos * mass, total_mass + mass),
+    );
+    weighted_sum / total_mass
+}
+
+/// A point at rest at `pos`
+fn at_rest(pos: Vector2D<Length>, mass: Mass) -> PointMass {
+    PointMass::new(
+        pos,
+        // units don't matter here, as long as we're initialising with zero-vector
+        Vector2D::<Velocity>::zero(),
+        Vector2D::<Acceleration>::zero(),
+        mass,
+    )
+}
+
+/// Offset of a lattice from the edge of a rectangle `cells` unit cells wide, in cells,
+/// centring the whole cells and keeping a quarter cell from the edge
+fn centring_margin(cells: f64) -> f64 {
+    // Tolerate rounding for rectangles that are a whole number of cells wide
+    let spare = cells - (cells + 1e-9).floor();
+    spare.max(0.) / 2. + 0.25
+}
+
+/// Uniform grid over a rectangle with cells at least `min_separation` wide, so that
+/// points closer than that lie in the same or adjacent cells
+struct SeparationGrid<'a> {
+    origin: Vector2D<Length>,
+    cell: Vector2D<Length>,
+    columns: usize,
+    rows: usize,
+    /// Indices of the points in each cell, row by row
+    cells: Vec<Vec<usize>>,
+    min_separation: Length,
+    simulation_box: Option<&'a SimulationBox>,
+}
+
+impl<'a> SeparationGrid<'a> {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn new(
+        bounds: &RectangularBounds,
+        count: usize,
+        min_separation: Length,
+        simulation_box: Option<&'a SimulationBox>,
+    ) -> Self {
+        // Minimum images in a triclinic box can connect cells that are not adjacent,
+        // so use a single cell there. Otherwise, about one point per cell suffices.
+        let triclinic =
+            simulation_box.is_some_and(|simulation_box| !simulation_box.is_orthorhombic());
+        let max_cells = if triclinic {
+            1
+        } else {
+            (count as f64).sqrt().ceil() as usize + 1
+        };
+        let cells_along = |extent: Length| {
+            ((extent / min_separation).get::<ratio>().floor() as usize).clamp(1, max_cells)
+        };
+        let columns = cells_along(bounds.x2 - bounds.x1);
+        let rows = cells_along(bounds.y2 - bounds.y1);
+        Self {
+            origin: Vector2D {
+                x: bounds.x1,
+                y: bounds.y1,
+            },
+            cell: Vector2D {
+                x: (bounds.x2 - bounds.x1) / columns as f64,
+                y: (bounds.y2 - bounds.y1) / rows as f64,
+            },
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+            min_separation,
+            simulation_box,
+        }
+    }
+
+    /// Column and row of the cell containing `pos`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn cell_of(&self, pos: Vector2D<Length>) -> (usize, usize) {
+        let index = |offset: Length, size: Length, len: usize| {
+            ((offset / size).get::<ratio>().max(0.) as usize).min(len - 1)
+        };
+        (
+            index(pos.x - self.origin.x, self.cell.x, self.columns),
+            index(pos.y - self.origin.y, self.cell.y, self.rows),
+        )
+    }
+
+    /// Cells adjacent to `index` along an axis of `len` cells, including itself.
+    /// These wrap around in a periodic box, which also covers regions smaller than it.
+    fn adjacent(&self, index: usize, len: usize) -> impl Iterator<Item = usize> + use<> {
+        let periodic = self.simulation_box.is_some();
+        [index + len - 1, index + len, index + len + 1]
+            .into_iter()
+            .filter_map(move |i| {
+                if periodic {
+                    Some(i % len)
+                } else {
+                    (len..2 * len).contains(&i).then(|| i - len)
+                }
+            })
+    }
+
+    /// Whether `pos` is at least `min_separation` from all points in the grid
+    fn is_free(&self, positions: &[Vector2D<Length>], pos: Vector2D<Length>) -> bool {
+        let (column, row) = self.cell_of(pos);
+        self.adjacent(row, self.rows).all(|r| {
+            self.adjacent(column, self.columns).all(|c| {
+                self.cells[r * self.columns + c].iter().all(|&i| {
+                    separation(pos, positions[i], self.simulation_box).mag() >= self.min_separation
+                })
+            })
+        })
+    }
+
+    /// Add the point with index `i` at `pos`
+    fn insert(&mut self, i: usize, pos: Vector2D<Length>) {
+        let (column, row) = self.cell_of(pos);
+        self.cells[row * self.columns + column].push(i);
     }
 }
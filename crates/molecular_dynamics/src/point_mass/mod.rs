@@ -1,18 +1,18 @@
 pub mod cluster;
+pub mod velocities;
 
+#[cfg(feature = "render")]
 use macroquad::color::Color;
 use std::fmt;
-use uom::si::{
-    f64::{Acceleration, Length, Mass, Ratio, Time, Velocity},
-    ratio::ratio,
-};
+use uom::si::f64::{Acceleration, Length, Mass, Velocity};
+#[cfg(feature = "render")]
+use visualization::simulation::{config::DisplayConfig, screen::Screen};
 
-use crate::physics::{
-    potential::Potential,
-    time_integration::{NaiveStep, StepType, VelocityVerletStep, VerletStep},
-};
-use physics_core::vector::Vector2D;
-use visualization::simulation::{config::SimulationConfig, screen::Screen};
+use crate::physics::potential::Potential;
+use physics_core::{config::SimulationConfig, vector::Vector2D};
+
+/// Index of a particle species, e.g. into the parameters of a `PairTable` potential
+pub type Species = usize;
 
 #[derive(Debug, Clone)]
 pub struct PointMass {
@@ -20,41 +20,32 @@ pub struct PointMass {
     pub vel: Vector2D<Velocity>,
     pub acc: Vector2D<Acceleration>,
     pub mass: Mass,
-    #[allow(dead_code)]
-    last_pos: Vector2D<Length>,
-    #[allow(dead_code)]
-    last_vel: Vector2D<Velocity>,
+    pub species: Species,
 }
 
 /// Initialisation
 impl PointMass {
-    /// Initialise a new `PointMass`
+    /// Initialise a new `PointMass` of species 0
     #[must_use]
     pub fn new(
         pos: Vector2D<Length>,
         vel: Vector2D<Velocity>,
         acc: Vector2D<Acceleration>,
         mass: Mass,
-        time_step: Time,
     ) -> Self {
         Self {
             pos,
             vel,
             acc,
             mass,
-            last_pos: Self::init_last_pos(pos, vel, acc, time_step),
-            last_vel: Vector2D::<Velocity>::zero(),
+            species: 0,
         }
     }
 
-    // approximation for initial R_(-1) ~ R_0 - tau * (V_0 + (tau * G_0) / 2)
-    fn init_last_pos(
-        first_pos: Vector2D<Length>,
-        first_vel: Vector2D<Velocity>,
-        first_acc: Vector2D<Acceleration>,
-        time_step: Time,
-    ) -> Vector2D<Length> {
-        first_pos - time_step * (first_vel + (time_step * first_acc) / 2.)
+    /// Returns the point with the given species
+    #[must_use]
+    pub fn with_species(self, species: Species) -> Self {
+        Self { species, ..self }
     }
 }
 
@@ -75,23 +66,6 @@ impl PointMass {
         self.acc += potential.force(self, other, config) / self.mass;
     }
 
-    /// Update position parameters using different methods
-    pub fn step(&mut self, step_type: Option<&StepType>, time_step: Time) {
-        let step_type = step_type.unwrap_or(&StepType::Naive);
-
-        match step_type {
-            StepType::Naive => {
-                self.naive_step(time_step);
-            }
-            StepType::Verlet => {
-                self.verlet_step(time_step);
-            }
-            StepType::VelocityVerlet => {
-                self.velocity_verlet_step(time_step);
-            }
-        }
-    }
-
     pub fn pos(&self) -> Vector2D<Length> {
         self.pos
     }
@@ -104,6 +78,9 @@ impl PointMass {
     pub fn mass(&self) -> Mass {
         self.mass
     }
+    pub fn species(&self) -> Species {
+        self.species
+    }
 
     pub fn set_pos(&mut self, new_value: Vector2D<Length>) {
         self.pos = new_value;
@@ -117,67 +94,26 @@ impl PointMass {
     pub fn set_mass(&mut self, new_value: Mass) {
         self.mass = new_value;
     }
+    pub fn set_species(&mut self, new_value: Species) {
+        self.species = new_value;
+    }
 
     /// Draws a circle to the Screen
+    #[cfg(feature = "render")]
     #[allow(clippy::cast_possible_truncation)]
-    pub fn draw(&self, config: &SimulationConfig, scale: Option<f32>, color: Color) {
+    pub fn draw(&self, config: &DisplayConfig, scale: Option<f32>, color: Color) {
         let screen_pos = Screen::world_to_screen(&self.pos, config);
         let radius = scale.unwrap_or(15.0 / config.mass_unit.get(self.mass()) as f32);
         macroquad::prelude::draw_circle(screen_pos.x, screen_pos.y, radius, color);
     }
 }
 
-impl NaiveStep for PointMass {
-    /// Naive update method
-    fn naive_step(&mut self, time_step: Time) {
-        self.pos += time_step * self.vel;
-        self.vel += time_step * self.acc;
-    }
-}
-
-impl VerletStep for PointMass {
-    /// Verlet update method
-    fn verlet_step(&mut self, time_step: Time) {
-        // save before updating
-        let prev_pos = self.last_pos;
-        let current_pos = self.pos;
-
-        // update pos
-        self.pos =
-            Ratio::new::<ratio>(2.) * self.pos - self.last_pos + time_step * (time_step * self.acc);
-
-        // update vel
-        self.vel = (self.pos - prev_pos) / (Ratio::new::<ratio>(2.) * time_step);
-
-        self.last_pos = current_pos;
-    }
-}
-
-impl VelocityVerletStep for PointMass {
-    /// Velocity Verlet update method variant
-    fn velocity_verlet_step(&mut self, time_step: Time) {
-        // save before updating
-        let current_pos = self.pos;
-        let current_vel = self.vel;
-
-        // update pos
-        self.pos +=
-            time_step * self.vel + (time_step / Ratio::new::<ratio>(2.0)) * (time_step * self.acc);
-
-        // update vel
-        self.vel += (time_step / Ratio::new::<ratio>(2.0)) * (Ratio::new::<ratio>(2.0) * self.acc); // this last term should actually be (G_(k+1) - G_k)
-
-        self.last_pos = current_pos;
-        self.last_vel = current_vel;
-    }
-}
-
 impl fmt::Display for PointMass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "PointMass {{ pos: {:?}, vel: {:?}, acc: {:?}, mass: {:?} }}",
-            self.pos, self.vel, self.acc, self.mass
+            "PointMass {{ pos: {:?}, vel: {:?}, acc: {:?}, mass: {:?}, species: {} }}",
+            self.pos, self.vel, self.acc, self.mass, self.species
         )
     }
 }
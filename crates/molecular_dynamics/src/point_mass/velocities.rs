@@ -0,0 +1,127 @@
+//! Initial velocities.
+//!
+//! `MaxwellBoltzmann` draws each velocity component from a normal distribution with
+//! variance k_B × T / m, the 2D Maxwell-Boltzmann distribution. The net linear
+//! momentum, and optionally the angular momentum about the center of mass, are then
+//! removed, so the cluster neither drifts nor spins. Finally all velocities are
+//! scaled so that the temperature, as reported by the `SimulationHandler` with 2
+//! degrees of freedom per particle, equals the target exactly.
+
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Energy, Length, Mass, Momentum, Ratio, ThermodynamicTemperature, Velocity},
+    ratio::ratio,
+    velocity::meter_per_second,
+};
+
+use crate::point_mass::{PointMass, cluster::center_of_mass};
+use physics_core::{constants::thermal_energy, vector::Vector2D};
+
+/// Maxwell-Boltzmann velocities at a target temperature
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaxwellBoltzmann {
+    #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+    pub temperature: ThermodynamicTemperature,
+    /// Also remove the angular momentum about the center of mass. Only conserved
+    /// with open boundaries, so leave off in a periodic box.
+    #[serde(default)]
+    pub remove_angular_momentum: bool,
+}
+
+impl MaxwellBoltzmann {
+    /// Velocities at `temperature`, removing only the net linear momentum
+    #[must_use]
+    pub fn new(temperature: ThermodynamicTemperature) -> Self {
+        Self {
+            temperature,
+            remove_angular_momentum: false,
+        }
+    }
+
+    /// Overwrite the velocities of `points`, drawing from `rng`
+    pub fn apply<'a>(
+        &self,
+        points: impl IntoIterator<Item = &'a mut PointMass>,
+        rng: &mut impl Rng,
+    ) {
+        let mut points: Vec<&mut PointMass> = points.into_iter().collect();
+        if points.is_empty() {
+            return;
+        }
+
+        for point in &mut points {
+            let spread: Velocity = (thermal_energy(self.temperature) / point.mass).sqrt();
+            let xi = Vector2D {
+                x: Ratio::new::<ratio>(StandardNormal.sample(rng)),
+                y: Ratio::new::<ratio>(StandardNormal.sample(rng)),
+            };
+            point.vel = spread * xi;
+        }
+
+        remove_linear_momentum(&mut points);
+        if self.remove_angular_momentum {
+            remove_angular_momentum(&mut points);
+        }
+
+        // Rescale to N_f × k_B × T / 2, unless no motion is left to scale
+        let kinetic_energy: Energy = points
+            .iter()
+            .map(|point| point.mass * (point.vel.x * point.vel.x + point.vel.y * point.vel.y) / 2.)
+            .sum();
+        #[allow(clippy::cast_precision_loss)]
+        let target = points.len() as f64 * thermal_energy(self.temperature);
+        let scale = (target / kinetic_energy).get::<ratio>().sqrt();
+        if !scale.is_finite() {
+            return;
+        }
+        let scale = Ratio::new::<ratio>(scale);
+        for point in &mut points {
+            point.vel = scale * point.vel;
+        }
+    }
+}
+
+/// Subtract the center-of-mass velocity: Σ m × V / Σ m
+fn remove_linear_momentum(points: &mut [&mut PointMass]) {
+    let total_mass: Mass = points.iter().map(|point| point.mass).sum();
+    let momentum = points
+        .iter()
+        .fold(Vector2D::<Momentum>::zero(), |total, point| {
+            total + point.mass * point.vel
+        });
+    let drift: Vector2D<Velocity> = momentum / total_mass;
+    for point in points {
+        point.vel -= drift;
+    }
+}
+
+/// Subtract the rigid rotation ω × (R - R_cm), with ω = L / I the angular velocity
+/// that carries the angular momentum L about the center of mass, for the moment of
+/// inertia I = Σ m × |R - R_cm|². Leaves the linear momentum unchanged.
+fn remove_angular_momentum(points: &mut [&mut PointMass]) {
+    let center = center_of_mass(points.iter().map(|point| (point.pos, point.mass)));
+    let offsets: Vec<Vector2D<Length>> = points.iter().map(|point| point.pos - center).collect();
+    let (angular_momentum, moment_of_inertia) = points.iter().zip(&offsets).fold(
+        (0., 0.),
+        |(angular_momentum, moment_of_inertia), (point, r)| {
+            (
+                angular_momentum + (point.mass * (r.x * point.vel.y - r.y * point.vel.x)).value,
+                moment_of_inertia + (point.mass * (r.x * r.x + r.y * r.y)).value,
+            )
+        },
+    );
+    if moment_of_inertia <= 0. {
+        return;
+    }
+
+    // uom tracks angular quantities as a separate kind, so work in SI base units
+    let angular_velocity = angular_momentum / moment_of_inertia;
+    for (point, r) in points.iter_mut().zip(&offsets) {
+        point.vel -= Vector2D {
+            x: Velocity::new::<meter_per_second>(-angular_velocity * r.y.value),
+            y: Velocity::new::<meter_per_second>(angular_velocity * r.x.value),
+        };
+    }
+}
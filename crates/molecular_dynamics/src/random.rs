@@ -0,0 +1,47 @@
+//! Seeded random number generation.
+//!
+//! All randomness of a simulation derives from the `seed` of its `SimulationConfig`.
+//! The handler draws from stream 0 of a `ChaCha8Rng` seeded with it, initial
+//! conditions from stream 1 and stochastic integrators from stream 2, so the three are
+//! independent and two runs with the same seed are bit-for-bit identical.
+//! Without a seed, one is drawn from the operating system; `Scenario::build` draws it
+//! once with `ensure_seed` and records it, so all three streams still share it.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use physics_core::config::SimulationConfig;
+
+/// Stream of the generator used for initial conditions
+const INITIALIZATION_STREAM: u64 = 1;
+
+/// Stream of the generator used by stochastic integrators
+pub(crate) const INTEGRATOR_STREAM: u64 = 2;
+
+/// The seed of `config`, drawing one from the operating system and recording it
+/// in `config` if it has none, so the run can be reproduced later
+pub fn ensure_seed(config: &mut SimulationConfig) -> u64 {
+    *config.seed.get_or_insert_with(rand::random)
+}
+
+/// Generator for initial conditions, seeded from `config` if it has a seed
+#[must_use]
+pub fn initialization_rng(config: &SimulationConfig) -> ChaCha8Rng {
+    stream_rng(config, INITIALIZATION_STREAM)
+}
+
+/// Generator for stochastic integrators such as `Langevin`, seeded from `config`
+/// if it has a seed
+#[must_use]
+pub fn integrator_rng(config: &SimulationConfig) -> ChaCha8Rng {
+    stream_rng(config, INTEGRATOR_STREAM)
+}
+
+fn stream_rng(config: &SimulationConfig, stream: u64) -> ChaCha8Rng {
+    let mut rng = match config.seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_os_rng(),
+    };
+    rng.set_stream(stream);
+    rng
+}
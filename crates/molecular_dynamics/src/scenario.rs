@@ -0,0 +1,490 @@
+//! Declarative scenario files.
+//!
+//! A scenario describes a complete simulation: the `SimulationConfig`, an optional
+//! periodic box, the particles, the pair potential and the integrator. Scenarios are
+//! read from TOML or RON, where quantities can be written with their unit, e.g.
+//! `time_step = "1 fs"` (see `physics_core::quantity_serde`). Plain numbers are in SI base units.
+//! Other sections, such as the `[display]` settings of the main binary, are ignored and
+//! can be read with `load_file`.
+//!
+//! ```toml
+//! integrator = "VelocityVerlet"
+//! velocities = { temperature = "120 K" }
+//!
+//! [config]
+//! time_step = "1 fs"
+//!
+//! [potential.LennardJones]
+//! epsilon = "0.0104 eV"
+//! sigma = "3.4 Å"
+//!
+//! [[particles]]
+//! UniformRandom = { count = 100, mass = "39.948 Da", min = ["-100 Å", "-100 Å"], max = ["100 Å", "100 Å"] }
+//! ```
+//!
+//! Besides single `Point`s and `UniformRandom` particles, `Lattice` and `Random`
+//! place particles on a lattice or at a minimum separation in a `RegionSpec`:
+//!
+//! ```toml
+//! [[particles]]
+//! Lattice = { lattice = "Hexagonal", spacing = { LatticeConstant = "3.816 Å" }, mass = "39.948 Da", region = { Circle = { center = ["0 Å", "0 Å"], radius = "40 Å" } } }
+//! ```
+//!
+//! Mixtures use a `LennardJonesMixture` potential, with parameters per species and
+//! unlike pairs mixed by a `MixingRule` unless listed in `pairs`. Each particle
+//! specification takes a `species`, 0 by default:
+//!
+//! ```toml
+//! [potential.LennardJonesMixture]
+//! mixing = "LorentzBerthelot"
+//! species = [{ epsilon = "0.0104 eV", sigma = "3.4 Å" }, { epsilon = "0.0184 eV", sigma = "4.1 Å" }]
+//! pairs = [{ species = [0, 1], parameters = { epsilon = "0.0138 eV", sigma = "3.75 Å" } }]
+//! ```
+
+use std::{fs, io, path::Path};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uom::si::f64::{
+    Acceleration, ArealNumberDensity, Frequency, Length, Mass, ThermodynamicTemperature, Velocity,
+};
+
+use crate::{
+    handler::SimulationHandler,
+    physics::{
+        potential::PairPotential,
+        simulation_box::SimulationBox,
+        time_integration::{
+            EmbeddedRungeKutta, Integrator, Langevin, Naive, SymplecticComposition, VelocityVerlet,
+            Verlet,
+        },
+    },
+    point_mass::{
+        PointMass, Species,
+        cluster::{CircularBounds, Cluster, Lattice, RectangularBounds, Region},
+        velocities::MaxwellBoltzmann,
+    },
+    random::{ensure_seed, initialization_rng, integrator_rng},
+};
+use physics_core::{config::SimulationConfig, vector::Vector2D};
+
+/// Time integration scheme of a scenario
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum IntegratorKind {
+    Naive,
+    Verlet,
+    #[default]
+    VelocityVerlet,
+    ForestRuth,
+    Yoshida4,
+    Yoshida6,
+    Pefrl,
+    /// Adaptive, using the tolerance of the configuration
+    Fehlberg,
+    /// Adaptive, using the tolerance of the configuration
+    DormandPrince,
+    /// Langevin dynamics, seeded from the configuration
+    Langevin {
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        friction: Frequency,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        temperature: ThermodynamicTemperature,
+    },
+}
+
+impl IntegratorKind {
+    /// Construct the integrator for the given configuration
+    #[must_use]
+    pub fn build(&self, config: &SimulationConfig) -> Box<dyn Integrator> {
+        match *self {
+            Self::Naive => Box::new(Naive),
+            Self::Verlet => Box::new(Verlet::default()),
+            Self::VelocityVerlet => Box::new(VelocityVerlet),
+            Self::ForestRuth => Box::new(SymplecticComposition::forest_ruth()),
+            Self::Yoshida4 => Box::new(SymplecticComposition::yoshida4()),
+            Self::Yoshida6 => Box::new(SymplecticComposition::yoshida6()),
+            Self::Pefrl => Box::new(SymplecticComposition::pefrl()),
+            Self::Fehlberg => Box::new(EmbeddedRungeKutta::fehlberg(config.adaptive_tolerance)),
+            Self::DormandPrince => Box::new(EmbeddedRungeKutta::dormand_prince(
+                config.adaptive_tolerance,
+            )),
+            Self::Langevin {
+                friction,
+                temperature,
+            } => Box::new(Langevin::with_rng(
+                friction,
+                temperature,
+                integrator_rng(config),
+            )),
+        }
+    }
+}
+
+/// Region of a scenario to place particles in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RegionSpec {
+    /// The rectangle with corners `min` and `max`
+    Rectangle {
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        min: Vector2D<Length>,
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        max: Vector2D<Length>,
+    },
+    /// The disk of `radius` around `center`, or an annulus if `inner_radius` is given
+    Circle {
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        center: Vector2D<Length>,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        radius: Length,
+        #[serde(
+            default,
+            deserialize_with = "physics_core::quantity_serde::deserialize"
+        )]
+        inner_radius: Length,
+    },
+}
+
+impl RegionSpec {
+    fn rectangle(min: Vector2D<Length>, max: Vector2D<Length>) -> RectangularBounds {
+        RectangularBounds {
+            x1: min.x,
+            x2: max.x,
+            y1: min.y,
+            y2: max.y,
+        }
+    }
+
+    fn circle(center: Vector2D<Length>, radius: Length, inner_radius: Length) -> CircularBounds {
+        CircularBounds {
+            center,
+            inner_radius,
+            outer_radius: radius,
+        }
+    }
+}
+
+impl Region for RegionSpec {
+    fn contains(&self, pos: Vector2D<Length>) -> bool {
+        match *self {
+            Self::Rectangle { min, max } => Self::rectangle(min, max).contains(pos),
+            Self::Circle {
+                center,
+                radius,
+                inner_radius,
+            } => Self::circle(center, radius, inner_radius).contains(pos),
+        }
+    }
+
+    fn bounding_rectangle(&self) -> RectangularBounds {
+        match *self {
+            Self::Rectangle { min, max } => Self::rectangle(min, max).bounding_rectangle(),
+            Self::Circle {
+                center,
+                radius,
+                inner_radius,
+            } => Self::circle(center, radius, inner_radius).bounding_rectangle(),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2D<Length> {
+        match *self {
+            Self::Rectangle { min, max } => Self::rectangle(min, max).sample(rng),
+            Self::Circle {
+                center,
+                radius,
+                inner_radius,
+            } => Self::circle(center, radius, inner_radius).sample(rng),
+        }
+    }
+}
+
+/// Spacing of a lattice in a scenario
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LatticeSpacing {
+    LatticeConstant(
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")] Length,
+    ),
+    /// Particles per area, e.g. `"2e18 m⁻²"`
+    Density(
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")] ArealNumberDensity,
+    ),
+}
+
+/// Particles to place in a scenario
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ParticleSpec {
+    /// A single particle, at rest unless a velocity is given
+    Point {
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        position: Vector2D<Length>,
+        #[serde(
+            default = "Vector2D::zero",
+            deserialize_with = "physics_core::quantity_serde::vector::deserialize"
+        )]
+        velocity: Vector2D<Velocity>,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        mass: Mass,
+        /// Species of the particle, see `PairTable`
+        #[serde(default)]
+        species: Species,
+    },
+    /// `count` particles at rest, placed uniformly at random in the rectangle
+    /// with corners `min` and `max`
+    UniformRandom {
+        count: usize,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        mass: Mass,
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        min: Vector2D<Length>,
+        #[serde(deserialize_with = "physics_core::quantity_serde::vector::deserialize")]
+        max: Vector2D<Length>,
+        /// Species of the particles, see `PairTable`
+        #[serde(default)]
+        species: Species,
+    },
+    /// `count` particles at rest, placed at random in `region` no closer than
+    /// `min_separation` to each other, see `Cluster::random_sequential_addition`
+    Random {
+        count: usize,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        mass: Mass,
+        region: RegionSpec,
+        #[serde(
+            default,
+            deserialize_with = "physics_core::quantity_serde::deserialize"
+        )]
+        min_separation: Length,
+        /// Species of the particles, see `PairTable`
+        #[serde(default)]
+        species: Species,
+    },
+    /// Particles at rest on the sites of a lattice within `region`
+    Lattice {
+        lattice: Lattice,
+        spacing: LatticeSpacing,
+        #[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]
+        mass: Mass,
+        region: RegionSpec,
+        /// Species of the particles, see `PairTable`
+        #[serde(default)]
+        species: Species,
+    },
+}
+
+impl ParticleSpec {
+    fn species(&self) -> Species {
+        match *self {
+            Self::Point { species, .. }
+            | Self::UniformRandom { species, .. }
+            | Self::Random { species, .. }
+            | Self::Lattice { species, .. } => species,
+        }
+    }
+
+    /// Append the described particles to `points`
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if random particles do not fit at
+    /// their minimum separation.
+    fn generate(
+        &self,
+        rng: &mut impl Rng,
+        simulation_box: Option<&SimulationBox>,
+        points: &mut Vec<PointMass>,
+    ) -> io::Result<()> {
+        let first = points.len();
+        match *self {
+            Self::Point {
+                position,
+                velocity,
+                mass,
+                ..
+            } => points.push(PointMass::new(
+                position,
+                velocity,
+                Vector2D::<Acceleration>::zero(),
+                mass,
+            )),
+            Self::UniformRandom {
+                count,
+                mass,
+                min,
+                max,
+                ..
+            } => {
+                let bounds = RectangularBounds {
+                    x1: min.x,
+                    x2: max.x,
+                    y1: min.y,
+                    y2: max.y,
+                };
+                let cluster = Cluster::uniform_random(&bounds, count, mass, rng);
+                points.extend(cluster.points.into_iter().map(|point| *point));
+            }
+            Self::Random {
+                count,
+                mass,
+                region,
+                min_separation,
+                ..
+            } => {
+                let cluster = Cluster::random_sequential_addition(
+                    &region,
+                    count,
+                    min_separation,
+                    mass,
+                    simulation_box,
+                    rng,
+                )
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("cannot fit {count} particles at their minimum separation"),
+                    )
+                })?;
+                points.extend(cluster.points.into_iter().map(|point| *point));
+            }
+            Self::Lattice {
+                lattice,
+                spacing,
+                mass,
+                region,
+                ..
+            } => {
+                let lattice_constant = match spacing {
+                    LatticeSpacing::LatticeConstant(lattice_constant) => lattice_constant,
+                    LatticeSpacing::Density(density) => lattice.lattice_constant(density),
+                };
+                let cluster = Cluster::lattice(&region, lattice, lattice_constant, mass);
+                points.extend(cluster.points.into_iter().map(|point| *point));
+            }
+        }
+        for point in &mut points[first..] {
+            point.species = self.species();
+        }
+        Ok(())
+    }
+}
+
+/// A complete simulation setup, as read from a scenario file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub config: SimulationConfig,
+    /// If given, use periodic boundary conditions in this box
+    #[serde(default)]
+    pub simulation_box: Option<SimulationBox>,
+    pub potential: PairPotential,
+    #[serde(default)]
+    pub integrator: IntegratorKind,
+    pub particles: Vec<ParticleSpec>,
+    /// If given, replace the velocities of all particles with Maxwell-Boltzmann ones
+    #[serde(default)]
+    pub velocities: Option<MaxwellBoltzmann>,
+}
+
+impl Scenario {
+    /// Parse a scenario from TOML
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if the scenario cannot be parsed.
+    pub fn from_toml(s: &str) -> io::Result<Self> {
+        toml::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Parse a scenario from RON
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if the scenario cannot be parsed.
+    pub fn from_ron(s: &str) -> io::Result<Self> {
+        ron::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Read a scenario file, in TOML or RON depending on its extension
+    ///
+    /// # Errors
+    /// Returns any error from reading, an error of kind `InvalidInput` for other
+    /// extensions, or one of kind `InvalidData` if the scenario cannot be parsed.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_file(path)
+    }
+
+    /// Generate the particles of the scenario. Random placement and velocities use
+    /// the initialization generator of the configuration, see `random`.
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if random particles do not fit at
+    /// their minimum separation, or if the potential has no parameters for the
+    /// species of some particles.
+    pub fn points(&self) -> io::Result<Vec<Box<PointMass>>> {
+        if let Some(species_count) = self.potential.species_count()
+            && let Some(species) = self
+                .particles
+                .iter()
+                .map(ParticleSpec::species)
+                .find(|&species| species >= species_count)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "particles of species {species}, but the potential has {species_count} species"
+                ),
+            ));
+        }
+
+        let mut rng = initialization_rng(&self.config);
+        let mut points = Vec::new();
+        for spec in &self.particles {
+            spec.generate(&mut rng, self.simulation_box.as_ref(), &mut points)?;
+        }
+        if let Some(velocities) = &self.velocities {
+            velocities.apply(&mut points, &mut rng);
+        }
+        Ok(points.into_iter().map(Box::new).collect())
+    }
+
+    /// Build a handler with the particles, box and integrator of the scenario,
+    /// ready to be stepped with its `config` and `potential`. Without a seed in the
+    /// configuration, one is drawn and recorded there first, so that initial
+    /// conditions, integrator and handler all derive from it.
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if a barostat is configured without
+    /// a simulation box, or if the particles cannot be generated, see `points`.
+    pub fn build(&mut self) -> io::Result<SimulationHandler> {
+        if self.config.barostat.is_some() && self.simulation_box.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "a barostat requires a simulation box",
+            ));
+        }
+
+        ensure_seed(&mut self.config);
+        let mut handler = SimulationHandler::new(self.points()?);
+        handler.set_integrator(self.integrator.build(&self.config));
+        handler.set_simulation_box(self.simulation_box);
+        Ok(handler)
+    }
+}
+
+/// Read a TOML or RON file, depending on its extension, into any type. Sections of a
+/// scenario file that `Scenario` ignores, such as `[display]`, can be read this way.
+///
+/// # Errors
+/// Returns any error from reading, an error of kind `InvalidInput` for other
+/// extensions, or one of kind `InvalidData` if the file cannot be parsed.
+pub fn load_file<T: DeserializeOwned>(path: impl AsRef<Path>) -> io::Result<T> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => {
+            toml::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        }
+        Some("ron") => {
+            ron::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown scenario format of {}", path.display()),
+        )),
+    }
+}
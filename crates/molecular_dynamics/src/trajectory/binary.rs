@@ -0,0 +1,493 @@
+//! Compact binary trajectories with random access to frames.
+//!
+//! All values are little-endian. A 64-byte header is followed by frames of a fixed
+//! size, so frame n starts at byte 64 + n × frame size and no separate index is needed.
+//!
+//! Header:
+//! magic "MDTRAJ\0\0" (8 bytes), version u32, precision u8 (4 or 8 bytes per value),
+//! flags u8 (bit 0: velocities), 2 reserved bytes, particle count u64,
+//! length unit in meters f64, length unit name (32 bytes, zero-padded)
+//!
+//! Frame:
+//! step u64, time in seconds f64, box origin x, origin y, lx, ly and xy as f64 in the
+//! length unit (NaN without a periodic box), then x, y of every position in the length
+//! unit, the image counts along both box edges of every particle as i32 and, if
+//! present, x, y of every velocity in the length unit per second.
+//!
+//! Positions are stored wrapped into the box. Together with the image counts, they
+//! give the unwrapped positions needed for e.g. the mean-squared displacement.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use uom::si::{
+    f64::{Length, Time, Velocity},
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+
+use crate::{
+    handler::SimulationHandler,
+    physics::simulation_box::{Image, SimulationBox},
+};
+use physics_core::{config::units::LengthUnit, vector::Vector2D};
+
+const MAGIC: &[u8; 8] = b"MDTRAJ\0\0";
+const VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 64;
+const UNIT_NAME_SIZE: usize = 32;
+const FLAG_VELOCITIES: u8 = 1;
+
+/// Floating point precision of positions and velocities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// `f32`, half the size
+    Single,
+    /// `f64`, lossless
+    Double,
+}
+
+impl Precision {
+    fn size(self) -> u64 {
+        match self {
+            Precision::Single => 4,
+            Precision::Double => 8,
+        }
+    }
+}
+
+/// Size in bytes of a single frame, or an error of kind `InvalidData` if it overflows
+fn frame_size(particle_count: u64, precision: Precision, velocities: bool) -> io::Result<u64> {
+    let arrays = if velocities { 2 } else { 1 };
+    // Step, time and box, then two image counts per particle
+    let per_particle = (arrays * 2 * precision.size()).checked_add(2 * 4);
+    per_particle
+        .and_then(|size| size.checked_mul(particle_count))
+        .and_then(|size| size.checked_add(8 + 8 + 5 * 8))
+        .ok_or_else(|| {
+            invalid_data(&format!(
+                "frames of {particle_count} particles are too large"
+            ))
+        })
+}
+
+/// Writes frames of a simulation to a binary trajectory
+#[derive(Debug)]
+pub struct BinaryTrajectoryWriter<W: Write> {
+    writer: W,
+    unit: LengthUnit,
+    precision: Precision,
+    velocities: bool,
+    /// Set by the first frame, which also writes the header
+    particle_count: Option<usize>,
+    frames: usize,
+}
+
+impl<W: Write> BinaryTrajectoryWriter<W> {
+    /// Initialise a writer of positions in `unit` at the given precision
+    #[must_use]
+    pub fn new(writer: W, unit: LengthUnit, precision: Precision) -> Self {
+        Self {
+            writer,
+            unit,
+            precision,
+            velocities: false,
+            particle_count: None,
+            frames: 0,
+        }
+    }
+
+    /// Also write velocities
+    #[must_use]
+    pub fn with_velocities(mut self) -> Self {
+        self.velocities = true;
+        self
+    }
+
+    /// Continue a trajectory of `particle_count` particles, whose header and first
+    /// `frames` frames precede the position of the writer. Unit, precision and
+    /// velocities must match those of the header.
+    #[must_use]
+    pub fn appending(mut self, particle_count: usize, frames: usize) -> Self {
+        self.particle_count = Some(particle_count);
+        self.frames = frames;
+        self
+    }
+
+    /// Number of frames written
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Append the current state of `handler` as a frame.
+    /// The first frame fixes the particle count of the trajectory.
+    ///
+    /// # Errors
+    /// Returns any error from writing to the underlying writer, or an error of kind
+    /// `InvalidInput` if the particle count differs from that of the first frame or an
+    /// image count does not fit in an `i32`.
+    pub fn write_frame(&mut self, handler: &SimulationHandler) -> io::Result<()> {
+        let positions = handler.positions();
+        match self.particle_count {
+            None => {
+                self.write_header(positions.len())?;
+                self.particle_count = Some(positions.len());
+            }
+            Some(count) if count != positions.len() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "frame has {} particles, trajectory has {count}",
+                        positions.len()
+                    ),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let unit = self.unit;
+        self.writer.write_all(&handler.step_count().to_le_bytes())?;
+        self.writer
+            .write_all(&handler.time().get::<second>().to_le_bytes())?;
+
+        let box_values = handler.simulation_box().map_or([f64::NAN; 5], |b| {
+            [
+                unit.get(b.origin.x),
+                unit.get(b.origin.y),
+                unit.get(b.lx),
+                unit.get(b.ly),
+                unit.get(b.xy),
+            ]
+        });
+        for value in box_values {
+            self.writer.write_all(&value.to_le_bytes())?;
+        }
+
+        for pos in positions {
+            self.write_value(unit.get(pos.x))?;
+            self.write_value(unit.get(pos.y))?;
+        }
+        for &(n_a, n_b) in handler.images() {
+            for count in [n_a, n_b] {
+                let count = i32::try_from(count).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("image count {count} does not fit in the trajectory"),
+                    )
+                })?;
+                self.writer.write_all(&count.to_le_bytes())?;
+            }
+        }
+        if self.velocities {
+            for vel in handler.velocities() {
+                // Distance covered in one second, in the chosen length unit
+                let displacement = *vel * Time::new::<second>(1.);
+                self.write_value(unit.get(displacement.x))?;
+                self.write_value(unit.get(displacement.y))?;
+            }
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer
+    ///
+    /// # Errors
+    /// Returns any error from flushing the writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_header(&mut self, particle_count: usize) -> io::Result<()> {
+        let mut unit_name = [0u8; UNIT_NAME_SIZE];
+        let name = format!("{:?}", self.unit);
+        let len = name.len().min(UNIT_NAME_SIZE);
+        unit_name[..len].copy_from_slice(&name.as_bytes()[..len]);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let precision = self.precision.size() as u8;
+        let flags = if self.velocities { FLAG_VELOCITIES } else { 0 };
+
+        self.writer.write_all(MAGIC)?;
+        self.writer.write_all(&VERSION.to_le_bytes())?;
+        self.writer.write_all(&[precision, flags, 0, 0])?;
+        self.writer
+            .write_all(&(particle_count as u64).to_le_bytes())?;
+        self.writer
+            .write_all(&self.unit.new(1.).get::<meter>().to_le_bytes())?;
+        self.writer.write_all(&unit_name)
+    }
+
+    fn write_value(&mut self, value: f64) -> io::Result<()> {
+        match self.precision {
+            #[allow(clippy::cast_possible_truncation)]
+            Precision::Single => self.writer.write_all(&(value as f32).to_le_bytes()),
+            Precision::Double => self.writer.write_all(&value.to_le_bytes()),
+        }
+    }
+}
+
+/// A single frame read back from a binary trajectory
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub step: u64,
+    pub time: Time,
+    pub simulation_box: Option<SimulationBox>,
+    /// Positions wrapped into the box
+    pub positions: Vec<Vector2D<Length>>,
+    /// Number of box lengths each particle has been wrapped by
+    pub images: Vec<Image>,
+    pub velocities: Option<Vec<Vector2D<Velocity>>>,
+}
+
+impl Frame {
+    /// Positions as if no wrapping took place, e.g. for computing diffusion
+    #[must_use]
+    pub fn unwrapped_positions(&self) -> Vec<Vector2D<Length>> {
+        match &self.simulation_box {
+            Some(simulation_box) => self
+                .positions
+                .iter()
+                .zip(&self.images)
+                .map(|(pos, image)| *pos + simulation_box.shift(*image))
+                .collect(),
+            None => self.positions.clone(),
+        }
+    }
+}
+
+/// Reads frames from a binary trajectory, in any order
+#[derive(Debug)]
+pub struct BinaryTrajectoryReader<R: Read + Seek> {
+    reader: R,
+    precision: Precision,
+    velocities: bool,
+    particle_count: u64,
+    frame_size: u64,
+    /// Length of the stored unit in meters
+    unit_scale: f64,
+    unit_name: String,
+    frames: u64,
+}
+
+impl<R: Read + Seek> BinaryTrajectoryReader<R> {
+    /// Read the header and count the complete frames
+    ///
+    /// # Errors
+    /// Returns any error from reading, or an error of kind `InvalidData` if the
+    /// header is not that of a supported binary trajectory.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; HEADER_SIZE as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+
+        if &header[0..8] != MAGIC {
+            return Err(invalid_data("not a binary trajectory"));
+        }
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap_or_default());
+        if version != VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported trajectory version {version}"
+            )));
+        }
+        let precision = match header[12] {
+            4 => Precision::Single,
+            8 => Precision::Double,
+            size => return Err(invalid_data(&format!("unsupported precision {size}"))),
+        };
+        let velocities = header[13] & FLAG_VELOCITIES != 0;
+        let particle_count = u64::from_le_bytes(header[16..24].try_into().unwrap_or_default());
+        let unit_scale = f64::from_le_bytes(header[24..32].try_into().unwrap_or_default());
+        let unit_name = String::from_utf8_lossy(&header[32..64])
+            .trim_end_matches('\0')
+            .to_string();
+
+        let frame_size = frame_size(particle_count, precision, velocities)?;
+        let file_size = reader.seek(SeekFrom::End(0))?;
+        let frames = file_size.saturating_sub(HEADER_SIZE) / frame_size;
+
+        Ok(Self {
+            reader,
+            precision,
+            velocities,
+            particle_count,
+            frame_size,
+            unit_scale,
+            unit_name,
+            frames,
+        })
+    }
+
+    /// Number of complete frames
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.frames
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    #[must_use]
+    pub fn particle_count(&self) -> u64 {
+        self.particle_count
+    }
+
+    #[must_use]
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    #[must_use]
+    pub fn has_velocities(&self) -> bool {
+        self.velocities
+    }
+
+    /// Name of the length unit the values are stored in, e.g. "Angstrom"
+    #[must_use]
+    pub fn unit_name(&self) -> &str {
+        &self.unit_name
+    }
+
+    /// Byte offset at which frame `index` starts, or would start if it is past the end
+    #[must_use]
+    pub fn frame_offset(&self, index: u64) -> u64 {
+        HEADER_SIZE.saturating_add(index.saturating_mul(self.frame_size))
+    }
+
+    /// Number of leading frames up to and including `step`, e.g. to drop the frames
+    /// written after a checkpoint. Frames are expected in the order they were written.
+    ///
+    /// # Errors
+    /// Returns any error from reading.
+    pub fn frames_through_step(&mut self, step: u64) -> io::Result<u64> {
+        let (mut low, mut high) = (0, self.frames);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            self.reader.seek(SeekFrom::Start(self.frame_offset(mid)))?;
+            if u64::from_le_bytes(self.read_bytes()?) <= step {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Seek to and read frame `index`
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidInput` if `index` is out of range,
+    /// or any error from reading.
+    pub fn read_frame(&mut self, index: u64) -> io::Result<Frame> {
+        if index >= self.frames {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("frame {index} out of range, trajectory has {}", self.frames),
+            ));
+        }
+        self.reader
+            .seek(SeekFrom::Start(self.frame_offset(index)))?;
+
+        let step = u64::from_le_bytes(self.read_bytes()?);
+        let time = Time::new::<second>(f64::from_le_bytes(self.read_bytes()?));
+
+        let mut box_values = [0.; 5];
+        for value in &mut box_values {
+            *value = f64::from_le_bytes(self.read_bytes()?);
+        }
+        let [origin_x, origin_y, lx, ly, xy] = box_values.map(|value| self.length(value));
+        let simulation_box = (!box_values[2].is_nan()).then(|| {
+            SimulationBox::triclinic(
+                Vector2D {
+                    x: origin_x,
+                    y: origin_y,
+                },
+                lx,
+                ly,
+                xy,
+            )
+        });
+
+        let positions = (0..self.particle_count)
+            .map(|_| {
+                let (x, y) = (self.read_value()?, self.read_value()?);
+                Ok(Vector2D {
+                    x: self.length(x),
+                    y: self.length(y),
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let images = (0..self.particle_count)
+            .map(|_| {
+                let n_a = i32::from_le_bytes(self.read_bytes()?);
+                let n_b = i32::from_le_bytes(self.read_bytes()?);
+                Ok((i64::from(n_a), i64::from(n_b)))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let velocities = if self.velocities {
+            let velocities = (0..self.particle_count)
+                .map(|_| {
+                    let (x, y) = (self.read_value()?, self.read_value()?);
+                    Ok(Vector2D {
+                        x: self.velocity(x),
+                        y: self.velocity(y),
+                    })
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            Some(velocities)
+        } else {
+            None
+        };
+
+        Ok(Frame {
+            step,
+            time,
+            simulation_box,
+            positions,
+            images,
+            velocities,
+        })
+    }
+
+    /// Iterate over all frames, starting from the first
+    pub fn frames(&mut self) -> impl Iterator<Item = io::Result<Frame>> + '_ {
+        (0..self.frames).map(|index| self.read_frame(index))
+    }
+
+    /// Consume the reader and return the underlying stream
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut bytes = [0u8; N];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn read_value(&mut self) -> io::Result<f64> {
+        match self.precision {
+            Precision::Single => Ok(f64::from(f32::from_le_bytes(self.read_bytes()?))),
+            Precision::Double => Ok(f64::from_le_bytes(self.read_bytes()?)),
+        }
+    }
+
+    fn length(&self, value: f64) -> Length {
+        Length::new::<meter>(value * self.unit_scale)
+    }
+
+    fn velocity(&self, value: f64) -> Velocity {
+        Velocity::new::<meter_per_second>(value * self.unit_scale)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
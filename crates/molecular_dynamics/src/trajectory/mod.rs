@@ -0,0 +1,10 @@
+//! Writing simulation trajectories to files.
+//!
+//! Writers take frames directly from a `SimulationHandler`, converting positions to
+//! a chosen `LengthUnit`, so runs can be inspected in external tools.
+
+pub mod binary;
+pub mod xyz;
+
+pub use binary::{BinaryTrajectoryReader, BinaryTrajectoryWriter, Frame, Precision};
+pub use xyz::{XyzFormat, XyzWriter};
@@ -0,0 +1,159 @@
+//! XYZ and extended XYZ trajectories, readable by OVITO, VMD and ASE.
+//!
+//! Each frame is a particle count, a comment line and one line per particle with its
+//! element and coordinates. Since the formats are three-dimensional, z is written as
+//! zero. The comment line holds the step and time; in extended XYZ it also declares
+//! the columns and, with a periodic box, the lattice vectors and the origin of the box:
+//! Lattice="lx 0 0 xy ly 0 0 0 1" Origin="ox oy 0" Properties=species:S:1:pos:R:3 Step=10 Time=1e-12 pbc="T T F"
+//! Times are in seconds and velocities in the chosen length unit per second.
+//! Particles of species s > 0 without an element of their own are labelled by the
+//! element followed by s, e.g. "X1", so the species of a mixture can be told apart.
+
+use std::io::{self, Write};
+
+use uom::si::{f64::Time, time::second};
+
+use crate::handler::SimulationHandler;
+use physics_core::config::units::LengthUnit;
+
+/// Flavour of the XYZ format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XyzFormat {
+    /// Plain XYZ, with the step and time in a free-form comment line
+    Plain,
+    /// Extended XYZ, with lattice, column properties and metadata as key-value pairs
+    Extended,
+}
+
+/// Writes frames of a simulation to an XYZ file
+#[derive(Debug)]
+pub struct XyzWriter<W: Write> {
+    writer: W,
+    unit: LengthUnit,
+    format: XyzFormat,
+    velocities: bool,
+    element: String,
+    species_elements: Vec<String>,
+    frames: usize,
+}
+
+impl<W: Write> XyzWriter<W> {
+    /// Initialise a writer of positions in `unit`, labelling every particle as "X"
+    #[must_use]
+    pub fn new(writer: W, unit: LengthUnit, format: XyzFormat) -> Self {
+        Self {
+            writer,
+            unit,
+            format,
+            velocities: false,
+            element: "X".to_string(),
+            species_elements: Vec::new(),
+            frames: 0,
+        }
+    }
+
+    /// Also write velocities, as three extra columns
+    #[must_use]
+    pub fn with_velocities(mut self) -> Self {
+        self.velocities = true;
+        self
+    }
+
+    /// Label every particle with the chemical `element`
+    #[must_use]
+    pub fn with_element(mut self, element: &str) -> Self {
+        self.element = element.to_string();
+        self
+    }
+
+    /// Label particles of species s with `elements[s]`, falling back to the
+    /// element of `with_element` for further species
+    #[must_use]
+    pub fn with_species_elements(mut self, elements: &[&str]) -> Self {
+        self.species_elements = elements.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Number of frames written
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Append the current state of `handler` as a frame
+    ///
+    /// # Errors
+    /// Returns any error from writing to the underlying writer.
+    pub fn write_frame(&mut self, handler: &SimulationHandler) -> io::Result<()> {
+        let unit = self.unit;
+        let step = handler.step_count();
+        let time = handler.time().get::<second>();
+
+        writeln!(self.writer, "{}", handler.positions().len())?;
+        match self.format {
+            XyzFormat::Plain => writeln!(self.writer, "step={step} time={time:e} s")?,
+            XyzFormat::Extended => {
+                if let Some(simulation_box) = handler.simulation_box() {
+                    // The third lattice vector is a unit vector along z. Positions are
+                    // absolute, so the cell is placed at the origin of the box.
+                    write!(
+                        self.writer,
+                        "Lattice=\"{} 0 0 {} {} 0 0 0 1\" Origin=\"{} {} 0\" ",
+                        unit.get(simulation_box.lx),
+                        unit.get(simulation_box.xy),
+                        unit.get(simulation_box.ly),
+                        unit.get(simulation_box.origin.x),
+                        unit.get(simulation_box.origin.y),
+                    )?;
+                }
+                let properties = if self.velocities {
+                    "species:S:1:pos:R:3:vel:R:3"
+                } else {
+                    "species:S:1:pos:R:3"
+                };
+                let pbc = if handler.simulation_box().is_some() {
+                    "T T F"
+                } else {
+                    "F F F"
+                };
+                writeln!(
+                    self.writer,
+                    "Properties={properties} Step={step} Time={time:e} pbc=\"{pbc}\""
+                )?;
+            }
+        }
+
+        for (i, pos) in handler.positions().iter().enumerate() {
+            let species = handler.species()[i];
+            match self.species_elements.get(species) {
+                Some(element) => write!(self.writer, "{element}")?,
+                None if species == 0 => write!(self.writer, "{}", self.element)?,
+                None => write!(self.writer, "{}{species}", self.element)?,
+            }
+            write!(self.writer, " {} {} 0", unit.get(pos.x), unit.get(pos.y))?;
+            if self.velocities {
+                // Distance covered in one second, in the chosen length unit
+                let displacement = handler.velocities()[i] * Time::new::<second>(1.);
+                write!(
+                    self.writer,
+                    " {} {} 0",
+                    unit.get(displacement.x),
+                    unit.get(displacement.y)
+                )?;
+            }
+            writeln!(self.writer)?;
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer
+    ///
+    /// # Errors
+    /// Returns any error from flushing the writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
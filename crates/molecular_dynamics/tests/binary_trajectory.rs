@@ -0,0 +1,92 @@
+use std::io::{self, Cursor};
+
+use molecular_dynamics::{
+    handler::SimulationHandler,
+    physics::{potential::LennardJones, simulation_box::SimulationBox},
+    point_mass::PointMass,
+    trajectory::{BinaryTrajectoryReader, BinaryTrajectoryWriter, Precision},
+};
+use physics_core::{
+    config::{SimulationConfigBuilder, units::LengthUnit},
+    vector::Vector2D,
+};
+use uom::si::{
+    energy::electronvolt,
+    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
+    length::angstrom,
+    mass::dalton,
+    time::femtosecond,
+    velocity::meter_per_second,
+};
+
+/// A lone argon atom crossing the edge of a 10 Å box several times
+#[test]
+fn frames_keep_image_counts() {
+    let config = SimulationConfigBuilder::default()
+        .time_step(Time::new::<femtosecond>(100.0))
+        .build()
+        .unwrap();
+    let potential = LennardJones {
+        epsilon: Energy::new::<electronvolt>(0.0104),
+        sigma: Length::new::<angstrom>(3.4),
+        cutoff: None,
+    };
+
+    let mut handler = SimulationHandler::new(vec![Box::new(PointMass::new(
+        Vector2D {
+            x: Length::new::<angstrom>(5.0),
+            y: Length::new::<angstrom>(5.0),
+        },
+        Vector2D {
+            x: Velocity::new::<meter_per_second>(2000.0),
+            y: Velocity::new::<meter_per_second>(-500.0),
+        },
+        Vector2D::<Acceleration>::zero(),
+        Mass::new::<dalton>(39.948),
+    ))]);
+    handler.set_simulation_box(Some(SimulationBox::orthorhombic(
+        Vector2D::zero(),
+        Length::new::<angstrom>(10.0),
+        Length::new::<angstrom>(10.0),
+    )));
+
+    let mut writer = BinaryTrajectoryWriter::new(
+        Cursor::new(Vec::new()),
+        LengthUnit::Angstrom,
+        Precision::Double,
+    );
+    let mut expected = Vec::new();
+    for _ in 0..20 {
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
+        writer.write_frame(&handler).unwrap();
+        expected.push(handler.unwrapped_positions()[0]);
+    }
+    assert_ne!(handler.images()[0], (0, 0), "atom never left the box");
+
+    let mut reader = BinaryTrajectoryReader::new(writer.into_inner().unwrap()).unwrap();
+    assert_eq!(reader.len(), 20);
+    for (frame, expected) in reader.frames().zip(expected) {
+        let unwrapped = frame.unwrap().unwrapped_positions()[0];
+        assert!(
+            (unwrapped - expected).mag() < Length::new::<angstrom>(1e-9),
+            "unwrapped {unwrapped:?}, expected {expected:?}"
+        );
+    }
+}
+
+/// A header whose frame size overflows is rejected rather than wrapping around
+#[test]
+fn oversized_frames_are_invalid() {
+    let mut header = Vec::new();
+    header.extend_from_slice(b"MDTRAJ\0\0");
+    header.extend_from_slice(&1u32.to_le_bytes());
+    header.extend_from_slice(&[8, 1, 0, 0]);
+    header.extend_from_slice(&u64::MAX.to_le_bytes());
+    header.extend_from_slice(&1e-10f64.to_le_bytes());
+    header.resize(64, 0);
+
+    let err = BinaryTrajectoryReader::new(Cursor::new(header)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
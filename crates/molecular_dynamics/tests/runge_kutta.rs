@@ -0,0 +1,68 @@
+use molecular_dynamics::{
+    handler::SimulationHandler,
+    physics::{potential::LennardJones, time_integration::EmbeddedRungeKutta},
+    point_mass::PointMass,
+};
+use physics_core::{config::SimulationConfigBuilder, vector::Vector2D};
+use uom::si::{
+    energy::electronvolt,
+    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
+    length::angstrom,
+    mass::dalton,
+    time::femtosecond,
+};
+
+/// Two argon atoms at rest, attracting each other
+fn argon_pair_at_rest() -> SimulationHandler {
+    let argon = |x: f64| {
+        Box::new(PointMass::new(
+            Vector2D {
+                x: Length::new::<angstrom>(x),
+                y: Length::new::<angstrom>(0.0),
+            },
+            Vector2D::<Velocity>::zero(),
+            Vector2D::<Acceleration>::zero(),
+            Mass::new::<dalton>(39.948),
+        ))
+    };
+    SimulationHandler::new(vec![argon(0.0), argon(4.5)])
+}
+
+/// Starting from rest, the default tolerance must not shrink the step to nothing
+#[test]
+fn adaptive_step_advances_from_rest() {
+    let config = SimulationConfigBuilder::default()
+        .time_step(Time::new::<femtosecond>(1.0))
+        .build()
+        .unwrap();
+    let potential = LennardJones {
+        epsilon: Energy::new::<electronvolt>(0.0104),
+        sigma: Length::new::<angstrom>(3.4),
+        cutoff: None,
+    };
+
+    let mut handler = argon_pair_at_rest();
+    handler.set_integrator(Box::new(EmbeddedRungeKutta::dormand_prince(
+        config.adaptive_tolerance,
+    )));
+
+    let mut time = handler.time();
+    for _ in 0..100 {
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
+        assert!(
+            handler.time() > time,
+            "time stuck at {} fs",
+            handler.time().get::<femtosecond>()
+        );
+        time = handler.time();
+    }
+
+    let separation = (handler.positions()[1] - handler.positions()[0]).mag();
+    assert!(
+        separation < Length::new::<angstrom>(4.5),
+        "atoms did not approach, {} Å apart",
+        separation.get::<angstrom>()
+    );
+}
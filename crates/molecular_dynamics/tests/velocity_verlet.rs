@@ -0,0 +1,78 @@
+use molecular_dynamics::{
+    handler::SimulationHandler,
+    physics::potential::{Gravity, Potential},
+    point_mass::PointMass,
+};
+use physics_core::{config::SimulationConfigBuilder, vector::Vector2D};
+use uom::si::{
+    energy::joule,
+    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
+    length::meter,
+    mass::kilogram,
+    time::second,
+    velocity::meter_per_second,
+};
+
+fn total_energy(handler: &mut SimulationHandler, potential: &Gravity) -> Energy {
+    handler.sync_to_points();
+    let (earth, moon) = (&handler.points[0], &handler.points[1]);
+
+    let kinetic = |point: &PointMass| {
+        let vel = point.vel();
+        0.5 * point.mass() * (vel.x * vel.x + vel.y * vel.y)
+    };
+
+    kinetic(earth) + kinetic(moon) + potential.energy(earth, moon)
+}
+
+/// Energy of the Earth-Moon orbit should oscillate, not drift, over many periods
+#[test]
+fn earth_moon_energy_drift_is_bounded() {
+    let config = SimulationConfigBuilder::default()
+        .time_step(Time::new::<second>(1000.0))
+        .build()
+        .unwrap();
+
+    let earth = PointMass::new(
+        Vector2D::<Length>::zero(),
+        Vector2D::<Velocity>::zero(),
+        Vector2D::<Acceleration>::zero(),
+        Mass::new::<kilogram>(5.972e24),
+    );
+    let moon = PointMass::new(
+        Vector2D {
+            x: Length::new::<meter>(3.844e8),
+            y: Length::new::<meter>(0.0),
+        },
+        Vector2D {
+            x: Velocity::new::<meter_per_second>(0.0),
+            y: Velocity::new::<meter_per_second>(1022.0),
+        },
+        Vector2D::<Acceleration>::zero(),
+        Mass::new::<kilogram>(7.342e22),
+    );
+
+    let potential = Gravity::default();
+    let mut handler = SimulationHandler::new(vec![Box::new(earth), Box::new(moon)]);
+    let initial_energy = total_energy(&mut handler, &potential);
+
+    // ~10 sidereal months of 27.3 days
+    let mut max_relative_error: f64 = 0.0;
+    for step in 1..=24_000 {
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
+
+        if step % 100 == 0 {
+            let energy = total_energy(&mut handler, &potential);
+            let relative_error = ((energy - initial_energy) / initial_energy).value.abs();
+            max_relative_error = max_relative_error.max(relative_error);
+        }
+    }
+
+    assert!(
+        max_relative_error < 1e-6,
+        "relative energy error {max_relative_error:e} (E₀ = {} J)",
+        initial_energy.get::<joule>()
+    );
+}
@@ -0,0 +1,176 @@
+//! Simulation configuration, independent of any renderer.
+//!
+//! `SimulationConfig` holds the parameters of the physics: time step, force softening
+//! and cap, neighbor lists, thermostats and barostats. Settings of the window and of
+//! drawing belong to the renderer, see `visualization::simulation::config::DisplayConfig`.
+
+pub mod units;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use uom::si::f64::{Force, Length, ThermodynamicTemperature, Time, Velocity};
+
+use crate::quantities::{Compressibility2D, Pressure2D};
+
+/// Error tolerance of adaptive time stepping.
+/// A step is accepted if its error estimate is below `absolute + relative × |value|`,
+/// with the larger |value| before and after the step, taken as root-mean-square over
+/// all particles, for both positions and velocities.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveTolerance {
+    pub relative: f64,
+    #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+    pub absolute_position: Length,
+    #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+    pub absolute_velocity: Velocity,
+    /// If given, steps are never made smaller than this, accepting a larger error instead
+    #[serde(
+        default,
+        deserialize_with = "crate::quantity_serde::option::deserialize"
+    )]
+    pub min_time_step: Option<Time>,
+    /// If given, steps are never made larger than this
+    #[serde(
+        default,
+        deserialize_with = "crate::quantity_serde::option::deserialize"
+    )]
+    pub max_time_step: Option<Time>,
+}
+
+impl Default for AdaptiveTolerance {
+    fn default() -> Self {
+        Self {
+            relative: 1e-8,
+            absolute_position: Length::default(),
+            absolute_velocity: Velocity::default(),
+            min_time_step: None,
+            max_time_step: None,
+        }
+    }
+}
+
+/// Method used to couple the system to a heat bath
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThermostatKind {
+    /// Rescale velocities to exactly the target temperature every step
+    VelocityRescale,
+    /// Weak coupling, relaxing the temperature exponentially towards the target.
+    /// Does not sample the canonical ensemble.
+    Berendsen {
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        relaxation_time: Time,
+    },
+    /// Canonical stochastic velocity rescaling (Bussi, Donadio and Parrinello)
+    Bussi {
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        relaxation_time: Time,
+    },
+    /// Deterministic canonical thermostat, a chain of `chain_length` coupled Nosé-Hoover
+    /// thermostats whose oscillations have a period of about `relaxation_time`
+    NoseHooverChain {
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        relaxation_time: Time,
+        chain_length: usize,
+    },
+}
+
+/// Thermostat holding the system at a target temperature
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThermostatConfig {
+    pub kind: ThermostatKind,
+    #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+    pub target_temperature: ThermodynamicTemperature,
+}
+
+/// Method used to control the pressure, by rescaling the periodic box
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BarostatKind {
+    /// Weak coupling, relaxing the pressure exponentially towards the target.
+    /// Does not sample the isobaric ensemble. The box is rescaled every `interval`
+    /// steps for the time elapsed since, as each rescale rebuilds the neighbor list.
+    Berendsen {
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        relaxation_time: Time,
+        /// Isothermal compressibility of the system, -(1/A)·∂A/∂P
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        compressibility: Compressibility2D,
+        interval: u32,
+    },
+    /// Metropolis moves of the box area, sampling the isothermal-isobaric ensemble.
+    /// Every `interval` steps, a uniform change in ln(area) of at most
+    /// `max_log_area_change` is attempted.
+    MonteCarlo {
+        #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+        temperature: ThermodynamicTemperature,
+        max_log_area_change: f64,
+        interval: u32,
+    },
+}
+
+/// Barostat holding the system at a target (2D) pressure
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BarostatConfig {
+    pub kind: BarostatKind,
+    #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+    pub target_pressure: Pressure2D,
+}
+
+#[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    /// uom::si::f32::Time object specifying time step between each simulation frame
+    #[serde(deserialize_with = "crate::quantity_serde::deserialize")]
+    pub time_step: Time,
+
+    /// The number of time steps to take each frame before rendering
+    #[builder(default = None)]
+    #[serde(default)]
+    pub time_steps_per_frame: Option<u8>,
+
+    /// If given, use a force-softening minimum distance
+    #[builder(default = None)]
+    #[serde(
+        default,
+        deserialize_with = "crate::quantity_serde::option::deserialize"
+    )]
+    pub force_softening_epsilon: Option<Length>,
+
+    /// If given, use a maximum allowed force
+    #[builder(default = None)]
+    #[serde(
+        default,
+        deserialize_with = "crate::quantity_serde::option::deserialize"
+    )]
+    pub force_cap: Option<Force>,
+
+    /// Skin thickness of the neighbor list used for potentials with a cutoff.
+    /// Defaults to 0.12 times the cutoff, i.e. 0.3σ for a Lennard-Jones cutoff of
+    /// 2.5σ. With a skin of zero, the list is rebuilt every time step.
+    #[builder(default = None)]
+    #[serde(
+        default,
+        deserialize_with = "crate::quantity_serde::option::deserialize"
+    )]
+    pub neighbor_list_skin: Option<Length>,
+
+    /// Error tolerance used by adaptive integrators
+    #[builder(default)]
+    #[serde(default)]
+    pub adaptive_tolerance: AdaptiveTolerance,
+
+    /// If given, control the temperature using a thermostat
+    #[builder(default = None)]
+    #[serde(default)]
+    pub thermostat: Option<ThermostatConfig>,
+
+    /// If given, control the pressure using a barostat. Requires a periodic simulation box.
+    #[builder(default = None)]
+    #[serde(default)]
+    pub barostat: Option<BarostatConfig>,
+
+    /// Seed of the random number generator used by stochastic methods.
+    /// Without a seed, one is drawn from the operating system.
+    #[builder(default = None)]
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
@@ -0,0 +1,22 @@
+//! Physical constants and the conversions built on them.
+
+use uom::si::{
+    energy::joule,
+    f64::{Energy, ThermodynamicTemperature},
+    thermodynamic_temperature::kelvin,
+};
+
+/// Boltzmann constant k_B, in J/K (exact since the 2019 SI redefinition)
+pub const BOLTZMANN_CONSTANT: f64 = 1.380_649e-23;
+
+/// Thermal energy k_B·T of the given temperature
+#[must_use]
+pub fn thermal_energy(temperature: ThermodynamicTemperature) -> Energy {
+    Energy::new::<joule>(BOLTZMANN_CONSTANT * temperature.get::<kelvin>())
+}
+
+/// Temperature T for which k_B·T equals the given energy
+#[must_use]
+pub fn temperature_from_thermal_energy(energy: Energy) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<kelvin>(energy.get::<joule>() / BOLTZMANN_CONSTANT)
+}
@@ -1 +1,5 @@
+pub mod config;
+pub mod constants;
+pub mod quantities;
+pub mod quantity_serde;
 pub mod vector;
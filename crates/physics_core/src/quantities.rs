@@ -0,0 +1,29 @@
+//! Derived quantities for two-dimensional systems.
+//!
+//! `uom` models three-dimensional space, so quantities like pressure change
+//! dimension in 2D. The aliases below use the default quantity kind, so they
+//! combine freely with the `uom::si::f64` types.
+
+use uom::si::{ISQ, Quantity, SI};
+use uom::typenum::{N1, N2, P1, P2, Z0};
+
+/// Pressure in 2D: force per unit length (M·T⁻², base unit N/m)
+pub type Pressure2D = Quantity<
+    ISQ<Z0, P1, N2, Z0, Z0, Z0, Z0>, // M·T⁻²
+    SI<f64>,
+    f64,
+>;
+
+/// Compressibility in 2D: relative area change per unit 2D pressure (M⁻¹·T², base unit m/N)
+pub type Compressibility2D = Quantity<
+    ISQ<Z0, N1, P2, Z0, Z0, Z0, Z0>, // M⁻¹·T²
+    SI<f64>,
+    f64,
+>;
+
+/// Shear viscosity in 2D: 2D pressure times time (M·T⁻¹, base unit N·s/m)
+pub type Viscosity2D = Quantity<
+    ISQ<Z0, P1, N1, Z0, Z0, Z0, Z0>, // M·T⁻¹
+    SI<f64>,
+    f64,
+>;
@@ -0,0 +1,196 @@
+//! Deserializing quantities written together with their unit.
+//!
+//! In human-readable formats such as TOML or RON, a quantity may be given either as
+//! a plain number in SI base units or as a string holding the value and a unit
+//! separated by a space, e.g. `"3.4 Å"`, `"1 fs"` or `"39.948 Da"`. Units are matched
+//! by abbreviation, singular or plural name, as by the `FromStr` implementations of
+//! `uom`. Binary formats store the plain SI value.
+//!
+//! Use with `#[serde(deserialize_with = "physics_core::quantity_serde::deserialize")]`, or the
+//! `option` and `vector` variants for optional quantities and vectors.
+
+use std::fmt;
+
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, IntoDeserializer, Visitor},
+};
+use uom::si::f64::{
+    Acceleration, ArealNumberDensity, Energy, Force, Frequency, Length, Mass,
+    ThermodynamicTemperature, Time, Velocity,
+};
+
+use crate::quantities::{Compressibility2D, Pressure2D};
+
+/// A quantity that can be parsed from a value followed by its unit
+pub trait ParseQuantity: Sized {
+    /// Parse `"<value> <unit>"`
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the value or unit are not recognised.
+    fn parse_quantity(s: &str) -> Result<Self, String>;
+}
+
+macro_rules! impl_parse_quantity {
+    ($($quantity:ty),* $(,)?) => {
+        $(
+            impl ParseQuantity for $quantity {
+                fn parse_quantity(s: &str) -> Result<Self, String> {
+                    s.parse().map_err(|err| format!("invalid quantity \"{s}\": {err}"))
+                }
+            }
+        )*
+    };
+}
+
+impl_parse_quantity!(
+    Acceleration,
+    ArealNumberDensity,
+    Energy,
+    Force,
+    Frequency,
+    Length,
+    Mass,
+    ThermodynamicTemperature,
+    Time,
+    Velocity,
+);
+
+/// Split `"<value> <numerator>/<denominator>"` into a quantity string for each unit
+fn split_ratio(s: &str) -> Result<(String, String), String> {
+    let (value, unit) = s
+        .split_once(' ')
+        .ok_or_else(|| format!("invalid quantity \"{s}\": no space between quantity and units"))?;
+    let (numerator, denominator) = unit
+        .trim()
+        .rsplit_once('/')
+        .ok_or_else(|| format!("invalid quantity \"{s}\": expected a unit of the form a/b"))?;
+    Ok((format!("{value} {numerator}"), format!("1 {denominator}")))
+}
+
+/// Parsed as a force per length, e.g. `"1.5 N/m"`
+impl ParseQuantity for Pressure2D {
+    fn parse_quantity(s: &str) -> Result<Self, String> {
+        let (force, length) = split_ratio(s)?;
+        Ok(Force::parse_quantity(&force)? / Length::parse_quantity(&length)?)
+    }
+}
+
+/// Parsed as a length per force, e.g. `"0.2 m/N"`
+impl ParseQuantity for Compressibility2D {
+    fn parse_quantity(s: &str) -> Result<Self, String> {
+        let (length, force) = split_ratio(s)?;
+        Ok(Length::parse_quantity(&length)? / Force::parse_quantity(&force)?)
+    }
+}
+
+/// Number in SI base units, or a string with the unit
+enum Repr {
+    Value(f64),
+    Text(String),
+}
+
+struct ReprVisitor;
+
+impl Visitor<'_> for ReprVisitor {
+    type Value = Repr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number in SI base units or a string such as \"3.4 Å\"")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Repr, E> {
+        Ok(Repr::Value(value))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Repr, E> {
+        Ok(Repr::Value(value as f64))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Repr, E> {
+        Ok(Repr::Value(value as f64))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Repr, E> {
+        Ok(Repr::Text(value.to_owned()))
+    }
+}
+
+/// Deserialize a quantity from an SI value or, in human-readable formats, a string with its unit
+///
+/// # Errors
+/// Returns an error if the input is neither, or the unit is not recognised.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + ParseQuantity,
+{
+    if !deserializer.is_human_readable() {
+        return T::deserialize(deserializer);
+    }
+    match deserializer.deserialize_any(ReprVisitor)? {
+        Repr::Value(value) => T::deserialize(value.into_deserializer()),
+        Repr::Text(text) => T::parse_quantity(&text).map_err(de::Error::custom),
+    }
+}
+
+/// Quantity deserialized by `deserialize`, for use inside other types
+struct WithUnit<T>(T);
+
+impl<'de, T> Deserialize<'de> for WithUnit<T>
+where
+    T: Deserialize<'de> + ParseQuantity,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(WithUnit)
+    }
+}
+
+/// Optional quantities. Combine with `#[serde(default)]` to allow the field to be omitted.
+pub mod option {
+    use serde::{Deserialize, Deserializer};
+
+    use super::{ParseQuantity, WithUnit};
+
+    /// Deserialize an optional quantity, see `quantity_serde::deserialize`
+    ///
+    /// # Errors
+    /// Returns an error if a present value is not a valid quantity.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + ParseQuantity,
+    {
+        Ok(Option::<WithUnit<T>>::deserialize(deserializer)?.map(|WithUnit(value)| value))
+    }
+}
+
+/// Vectors of quantities, given as `{ x = ..., y = ... }` or `[x, y]`
+pub mod vector {
+    use serde::{Deserialize, Deserializer};
+
+    use super::{ParseQuantity, WithUnit};
+    use crate::vector::Vector2D;
+
+    #[derive(Deserialize)]
+    #[serde(bound = "WithUnit<T>: Deserialize<'de>")]
+    struct Components<T> {
+        x: WithUnit<T>,
+        y: WithUnit<T>,
+    }
+
+    /// Deserialize a vector with each component as in `quantity_serde::deserialize`
+    ///
+    /// # Errors
+    /// Returns an error if a component is not a valid quantity.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vector2D<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + ParseQuantity,
+    {
+        let Components { x, y } = Components::deserialize(deserializer)?;
+        Ok(Vector2D { x: x.0, y: y.0 })
+    }
+}
@@ -3,11 +3,12 @@
 //! This module provides a `Vector2D` struct with basic vector operations
 //! including addition, subtraction, scalar multiplication, and magnitude calculation.
 
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 use uom::si::Quantity;
 
 /// A generic-typed 2D vector with x and y components.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector2D<Q> {
     pub x: Q,
     pub y: Q,
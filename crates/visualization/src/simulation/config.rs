@@ -1,22 +1,19 @@
-use derive_builder::Builder;
-use uom::si::f64::{Force, Length, Time};
-
-use macroquad::prelude::*;
+//! Renderer configuration.
+//!
+//! `DisplayConfig` holds the settings of the window and of drawing, kept apart from the
+//! physics in `physics_core::config::SimulationConfig`. Positions are drawn in
+//! `length_unit`, scaled by `pixels_per_length`, and default particle radii shrink
+//! with the mass in `mass_unit`.
 
-use crate::simulation::{
-    // screen::{Screen, ScreenPosition},
-    units::{LengthUnit, MassUnit},
-};
-
-#[derive(Debug, Builder)]
-pub struct SimulationConfig {
-    /// uom::si::f32::Time object specifying time step between each simulation frame
-    pub time_step: Time,
+use derive_builder::Builder;
+use macroquad::window::set_fullscreen;
+use serde::{Deserialize, Serialize};
 
-    /// The number of time steps to take each frame before rendering
-    #[builder(default = None)]
-    pub time_steps_per_frame: Option<u8>,
+use crate::simulation::units::{LengthUnit, MassUnit};
 
+#[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
     /// Length unit used in drawing
     #[builder(default = "LengthUnit::Meter")]
     pub length_unit: LengthUnit,
@@ -36,31 +33,18 @@ pub struct SimulationConfig {
     /// Whether to display stats
     #[builder(default = false)]
     pub display_stats: bool,
-
-    /// If given, use a force-softening minimum distance
-    #[builder(default = None)]
-    pub force_softening_epsilon: Option<Length>,
-
-    /// If given, use a maximum allowed force
-    #[builder(default = None)]
-    pub force_cap: Option<Force>,
 }
 
-impl SimulationConfig {
-    pub fn simulation_setup(&self) {
-        set_fullscreen(self.init_fullscreen);
+/// The defaults of the builder, which sets every field
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfigBuilder::default()
+            .build()
+            .expect("every field has a builder default")
     }
+}
 
-    // pub fn loop_setup(&self) {
-    //     if config.display_stats {
-    //         Screen::display_stats(
-    //             &[("time", &(passed_time.value as f32))],
-    //             ScreenPosition::TopRight,
-    //             None,
-    //             None,
-    //             None,
-    //             None,
-    //         );
-    //     }
-    // }
+/// Apply the window settings of `config`
+pub fn simulation_setup(config: &DisplayConfig) {
+    set_fullscreen(config.init_fullscreen);
 }
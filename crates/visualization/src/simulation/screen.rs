@@ -3,7 +3,7 @@ use macroquad::text::measure_text;
 
 use uom::si::f64::Length;
 
-use crate::simulation::config::SimulationConfig;
+use crate::simulation::config::DisplayConfig;
 use physics_core::vector::Vector2D;
 
 pub struct Screen;
@@ -24,7 +24,7 @@ impl Screen {
     /// Convert world coordinates to screen coordinates using given config
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
-    pub fn world_to_screen(pos: &Vector2D<Length>, config: &SimulationConfig) -> Vec2 {
+    pub fn world_to_screen(pos: &Vector2D<Length>, config: &DisplayConfig) -> Vec2 {
         let x = config.length_unit.get(pos.x) * config.pixels_per_length;
         let y = config.length_unit.get(pos.y) * config.pixels_per_length;
 
@@ -1,21 +1,21 @@
 use molecular_dynamics::{
-    physics::{potential::LennardJones, time_integration::StepType},
-    point_mass::PointMass,
+    handler::SimulationHandler,
+    physics::{potential::LennardJones, time_integration::Verlet},
+    point_mass::cluster::{Cluster, RectangularBounds},
+    random::initialization_rng,
 };
-use physics_core::vector::Vector2D;
+use physics_core::config::SimulationConfigBuilder;
 use visualization::simulation::{
-    config::SimulationConfigBuilder,
+    config::DisplayConfigBuilder,
     units::{LengthUnit, MassUnit},
 };
 
 use uom::si::{
-    acceleration::meter_per_second_squared,
     energy::electronvolt,
-    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
+    f64::{Energy, Length, Mass, Time},
     length::angstrom,
     mass::dalton,
     time::femtosecond,
-    velocity::atomic_unit_of_velocity,
 };
 
 use macroquad::prelude::*;
@@ -25,61 +25,51 @@ async fn main() {
     // Simulation config for Argon
     let config = SimulationConfigBuilder::default()
         .time_step(Time::new::<femtosecond>(1.0))
+        .force_softening_epsilon(Some(LengthUnit::Angstrom.new(1e-3)))
+        .seed(Some(42))
+        .build()
+        .unwrap();
+    let display = DisplayConfigBuilder::default()
         .length_unit(LengthUnit::Angstrom)
         .mass_unit(MassUnit::Dalton)
         .pixels_per_length(4.0)
-        .force_softening_epsilon(Some(LengthUnit::Angstrom.new(1e-3)))
         .build()
         .unwrap();
 
-    // Initialize argon atoms
+    // Initialize argon atoms at random, reproducibly through the seed of the config
     let max_bound = 100.0;
-    let mut points: Vec<Box<PointMass>> = Vec::new();
-    for _i in 0..100 {
-        points.push(Box::new(PointMass::new(
-            Vector2D {
-                x: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-                y: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-            },
-            Vector2D {
-                x: Velocity::new::<atomic_unit_of_velocity>(0.0),
-                y: Velocity::new::<atomic_unit_of_velocity>(0.0),
-            },
-            Vector2D {
-                x: Acceleration::new::<meter_per_second_squared>(0.0),
-                y: Acceleration::new::<meter_per_second_squared>(0.0),
-            },
-            Mass::new::<dalton>(39.948),
-            config.time_step,
-        )));
-    }
+    let bounds = RectangularBounds {
+        x1: Length::new::<angstrom>(-max_bound),
+        x2: Length::new::<angstrom>(max_bound),
+        y1: Length::new::<angstrom>(-max_bound),
+        y2: Length::new::<angstrom>(max_bound),
+    };
+    let mut rng = initialization_rng(&config);
+    let points = Cluster::new(&bounds, 100, Mass::new::<dalton>(39.948), &mut rng).points;
+
+    // Create simulation handler, using the base Verlet method
+    let mut handler = SimulationHandler::new(points);
+    handler.set_integrator(Box::new(Verlet::default()));
 
     // Lennard-Jones potential for Argon
     let potential = LennardJones {
         epsilon: Energy::new::<electronvolt>(0.0104),
         sigma: Length::new::<angstrom>(3.4),
+        cutoff: None,
     };
 
     let color = WHITE;
 
     loop {
-        clear_background(BLACK);
-
-        for i in 0..points.len() {
-            let (left, right) = points.split_at_mut(i);
-            let (current, right) = right.split_first_mut().unwrap();
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
 
-            current.reset_forces();
-
-            // Apply forces from objects before and after current
-            for other in left.iter().chain(right.iter()) {
-                current.apply_force(&potential, other.as_ref(), &config);
-            }
-        }
+        clear_background(BLACK);
 
-        for point in &mut points {
-            point.step(Some(&StepType::Verlet), config.time_step);
-            point.draw(&config, Some(10.), color);
+        handler.sync_to_points();
+        for point in &handler.points {
+            point.draw(&display, Some(10.), color);
         }
 
         next_frame().await;
@@ -1,22 +1,28 @@
 use molecular_dynamics::{
     handler::SimulationHandler,
-    physics::{potential::LennardJones, time_integration::StepType},
-    point_mass::PointMass,
+    physics::{
+        potential::{Cutoff, CutoffMode, LennardJones},
+        simulation_box::SimulationBox,
+    },
+    point_mass::{
+        cluster::{Cluster, RectangularBounds},
+        velocities::MaxwellBoltzmann,
+    },
+    random::initialization_rng,
 };
-use physics_core::vector::Vector2D;
+use physics_core::{config::SimulationConfigBuilder, vector::Vector2D};
 use visualization::simulation::{
-    config::SimulationConfigBuilder,
+    config::DisplayConfigBuilder,
     units::{LengthUnit, MassUnit},
 };
 
 use uom::si::{
-    acceleration::meter_per_second_squared,
     energy::electronvolt,
-    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
+    f64::{Energy, Length, Mass, ThermodynamicTemperature, Time},
     length::angstrom,
     mass::dalton,
+    thermodynamic_temperature::kelvin,
     time::femtosecond,
-    velocity::atomic_unit_of_velocity,
 };
 
 use macroquad::prelude::*;
@@ -26,54 +32,61 @@ async fn main() {
     // Simulation config for Argon
     let config = SimulationConfigBuilder::default()
         .time_step(Time::new::<femtosecond>(1.0))
+        .time_steps_per_frame(Some(10))
+        .seed(Some(42))
+        .build()
+        .unwrap();
+    let display = DisplayConfigBuilder::default()
         .length_unit(LengthUnit::Angstrom)
         .mass_unit(MassUnit::Dalton)
         .pixels_per_length(0.4)
-        .time_steps_per_frame(Some(10))
         .build()
         .unwrap();
 
-    // Initialize argon atoms
+    // Initialize argon atoms at random at 120 K, reproducibly through the seed of the config
+    let mut rng = initialization_rng(&config);
     let max_bound = 1000.0;
-    let mut points: Vec<Box<PointMass>> = Vec::new();
-    for _i in 0..1000 {
-        points.push(Box::new(PointMass::new(
-            Vector2D {
-                x: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-                y: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-            },
-            Vector2D {
-                x: Velocity::new::<atomic_unit_of_velocity>(0.0),
-                y: Velocity::new::<atomic_unit_of_velocity>(0.0),
-            },
-            Vector2D {
-                x: Acceleration::new::<meter_per_second_squared>(0.0),
-                y: Acceleration::new::<meter_per_second_squared>(0.0),
-            },
-            Mass::new::<dalton>(39.948),
-            config.time_step,
-        )));
-    }
+    let bounds = RectangularBounds {
+        x1: Length::new::<angstrom>(-max_bound),
+        x2: Length::new::<angstrom>(max_bound),
+        y1: Length::new::<angstrom>(-max_bound),
+        y2: Length::new::<angstrom>(max_bound),
+    };
+    let mut cluster = Cluster::uniform_random(&bounds, 1000, Mass::new::<dalton>(39.948), &mut rng);
+    cluster.thermalize(
+        &MaxwellBoltzmann::new(ThermodynamicTemperature::new::<kelvin>(120.)),
+        &mut rng,
+    );
+    let points = cluster.points;
 
-    // Create simulation handler
+    // Create simulation handler, with periodic boundaries around the initial region
     let mut handler = SimulationHandler::new(points);
+    handler.set_simulation_box(Some(SimulationBox::orthorhombic(
+        Vector2D {
+            x: Length::new::<angstrom>(-max_bound),
+            y: Length::new::<angstrom>(-max_bound),
+        },
+        Length::new::<angstrom>(2. * max_bound),
+        Length::new::<angstrom>(2. * max_bound),
+    )));
 
-    // Lennard-Jones potential for Argon
+    // Lennard-Jones potential for Argon, cut off at 2.5σ
     let potential = LennardJones {
         epsilon: Energy::new::<electronvolt>(0.0104),
         sigma: Length::new::<angstrom>(3.4),
+        cutoff: Some(Cutoff {
+            radius: Length::new::<angstrom>(8.5),
+            mode: CutoffMode::ShiftedForce,
+        }),
     };
 
     let color = WHITE;
 
     loop {
         // Run multiple physics steps
-        handler.step_physics(
-            &config,
-            &potential,
-            config.time_step,
-            StepType::VelocityVerlet,
-        );
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
 
         clear_background(BLACK);
 
@@ -82,7 +95,7 @@ async fn main() {
 
         // Draw all objects
         for obj in &handler.points {
-            obj.draw(&config, Some(1.), color);
+            obj.draw(&display, Some(1.), color);
         }
 
         next_frame().await;
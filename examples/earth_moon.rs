@@ -1,13 +1,14 @@
 use molecular_dynamics::{
+    handler::SimulationHandler,
     physics::{
         potential::{Gravity, Potential},
-        time_integration::StepType,
+        time_integration::Verlet,
     },
     point_mass::PointMass,
 };
-use physics_core::vector::Vector2D;
+use physics_core::{config::SimulationConfigBuilder, vector::Vector2D};
 use visualization::simulation::{
-    config::SimulationConfigBuilder,
+    config::DisplayConfigBuilder,
     units::{LengthUnit, MassUnit},
 };
 
@@ -27,6 +28,9 @@ async fn main() {
     // Simulation config for Earth-Moon system
     let config = SimulationConfigBuilder::default()
         .time_step(Time::new::<second>(1000.0))
+        .build()
+        .unwrap();
+    let display = DisplayConfigBuilder::default()
         .length_unit(LengthUnit::Meter)
         .mass_unit(MassUnit::Kilogram)
         .pixels_per_length(400.0 / 3.844e8)
@@ -42,7 +46,7 @@ async fn main() {
     let moon_orbital_velocity = Velocity::new::<meter_per_second>(1022.0);
 
     // Earth at origin
-    let mut earth = PointMass::new(
+    let earth = PointMass::new(
         Vector2D {
             x: Length::new::<meter>(0.0),
             y: Length::new::<meter>(0.0),
@@ -56,11 +60,10 @@ async fn main() {
             y: Acceleration::new::<meter_per_second_squared>(0.0),
         },
         earth_mass,
-        config.time_step,
     );
 
     // Moon
-    let mut moon = PointMass::new(
+    let moon = PointMass::new(
         Vector2D {
             x: earth_moon_distance,
             y: Length::new::<meter>(0.0),
@@ -74,28 +77,26 @@ async fn main() {
             y: Acceleration::new::<meter_per_second_squared>(0.0),
         },
         moon_mass,
-        config.time_step,
     );
 
     // Newtonian gravity potential
     let potential = Gravity::default();
 
     // Update algorithm
-    let step_type = StepType::Verlet;
+    let mut handler = SimulationHandler::new(vec![Box::new(earth), Box::new(moon)]);
+    handler.set_integrator(Box::new(Verlet::default()));
 
     loop {
         clear_background(BLACK);
 
-        earth.reset_forces();
-        earth.apply_force(&potential, &moon, &config);
-        moon.reset_forces();
-        moon.apply_force(&potential, &earth, &config);
-
-        earth.step(Some(&step_type), config.time_step);
-        moon.step(Some(&step_type), config.time_step);
+        handler
+            .step_physics(&config, &potential, config.time_step)
+            .unwrap();
+        handler.sync_to_points();
 
-        earth.draw(&config, Some(20.), BLUE);
-        moon.draw(&config, Some(10.), WHITE);
+        let (earth, moon) = (&handler.points[0], &handler.points[1]);
+        earth.draw(&display, Some(20.), BLUE);
+        moon.draw(&display, Some(10.), WHITE);
 
         next_frame().await;
     }
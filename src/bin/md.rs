@@ -0,0 +1,552 @@
+//! Headless command-line runner, for running simulations without a window.
+//!
+//! `md run` steps a scenario, `md resume` continues from a checkpoint and
+//! `md analyze` computes g(r) and the mean-squared displacement of a binary trajectory.
+//! A resumed run keeps the entries of existing trajectory and energy files up to the
+//! checkpointed step, drops any written after it and appends its own.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use clap::{Args, Parser, Subcommand};
+use molecular_dynamics::{
+    analysis::{MeanSquaredDisplacement, RadialDistribution},
+    handler::SimulationHandler,
+    physics::potential::PairPotential,
+    scenario::Scenario,
+    trajectory::{BinaryTrajectoryReader, BinaryTrajectoryWriter, Precision, XyzFormat, XyzWriter},
+};
+use physics_core::config::{SimulationConfig, units::LengthUnit};
+use serde::{Deserialize, de::value::StrDeserializer};
+use uom::si::{
+    energy::joule, f64::Time, ratio::ratio, thermodynamic_temperature::kelvin, time::second,
+};
+
+#[derive(Parser)]
+#[command(about = "Run molecular dynamics simulations without rendering")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a scenario from its initial state
+    Run {
+        /// Scenario file, in TOML or RON
+        scenario: PathBuf,
+        #[command(flatten)]
+        output: RunArgs,
+    },
+    /// Continue a run from a checkpoint
+    Resume {
+        /// Checkpoint written by `run` or `resume`
+        #[arg(value_name = "CHECKPOINT")]
+        from: PathBuf,
+        /// Scenario the checkpointed run was started from, providing its potential and integrator.
+        /// The integrator is built with the checkpointed configuration.
+        #[arg(long)]
+        scenario: PathBuf,
+        #[command(flatten)]
+        output: RunArgs,
+    },
+    /// Compute g(r) and the mean-squared displacement of a binary trajectory
+    Analyze {
+        /// Binary trajectory written by `run` or `resume`
+        trajectory: PathBuf,
+        /// Write g(r) to this CSV file. Requires a periodic box.
+        #[arg(long)]
+        rdf: Option<PathBuf>,
+        /// Number of bins of g(r)
+        #[arg(long, default_value_t = 200)]
+        bins: usize,
+        /// Largest distance of g(r), in the length unit of the trajectory.
+        /// Defaults to half the smallest width of the box.
+        #[arg(long)]
+        max_radius: Option<f64>,
+        /// Write the mean-squared displacement to this CSV file
+        #[arg(long)]
+        msd: Option<PathBuf>,
+        /// Number of frames to skip at the start, e.g. for equilibration
+        #[arg(long, default_value_t = 0)]
+        skip: u64,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Number of steps to take
+    #[arg(long)]
+    steps: u64,
+    /// Write a trajectory to this file: extended XYZ for `.xyz`, the binary format otherwise
+    #[arg(long)]
+    trajectory: Option<PathBuf>,
+    /// Steps between trajectory frames
+    #[arg(long, default_value_t = 100)]
+    trajectory_interval: u64,
+    /// Include velocities in the trajectory
+    #[arg(long)]
+    velocities: bool,
+    /// Length unit of the trajectory, named as in scenario files
+    #[arg(long, default_value = "Angstrom", value_parser = length_unit_named)]
+    length_unit: LengthUnit,
+    /// Write kinetic, potential and total energy to this CSV file
+    #[arg(long)]
+    energy: Option<PathBuf>,
+    /// Steps between energy log lines
+    #[arg(long, default_value_t = 100)]
+    energy_interval: u64,
+    /// Write a checkpoint to this file at the end of the run
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+    /// Also write the checkpoint every this many steps
+    #[arg(long)]
+    checkpoint_interval: Option<u64>,
+    /// Steps between progress reports
+    #[arg(long, default_value_t = 1000)]
+    progress_interval: u64,
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run { scenario, output } => run(&scenario, None, &output),
+        Command::Resume {
+            from,
+            scenario,
+            output,
+        } => run(&scenario, Some(&from), &output),
+        Command::Analyze {
+            trajectory,
+            rdf,
+            bins,
+            max_radius,
+            msd,
+            skip,
+        } => analyze(
+            &trajectory,
+            rdf.as_deref(),
+            bins,
+            max_radius,
+            msd.as_deref(),
+            skip,
+        ),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Trajectory output in either format
+enum TrajectoryOutput {
+    Xyz(XyzWriter<BufWriter<File>>),
+    Binary(BinaryTrajectoryWriter<BufWriter<File>>),
+}
+
+impl TrajectoryOutput {
+    /// Create the trajectory at `path` or, when resuming from `resume_step`, continue
+    /// an existing one after its frames up to that step. Also returns the number of
+    /// frames kept.
+    fn open(
+        path: &Path,
+        unit: LengthUnit,
+        velocities: bool,
+        resume_step: Option<u64>,
+    ) -> io::Result<(Self, usize)> {
+        // A new trajectory is empty, so nothing is kept
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(resume_step.is_none())
+            .open(path)?;
+        let step = resume_step.unwrap_or_default();
+
+        if path.extension().is_some_and(|ext| ext == "xyz") {
+            let (file, frames) = keep_xyz_frames(file, step)?;
+            let xyz = XyzWriter::new(BufWriter::new(file), unit, XyzFormat::Extended);
+            let xyz = if velocities {
+                xyz.with_velocities()
+            } else {
+                xyz
+            };
+            Ok((Self::Xyz(xyz), frames))
+        } else {
+            let (file, kept) = keep_binary_frames(file, step, unit, velocities)?;
+            let binary = BinaryTrajectoryWriter::new(BufWriter::new(file), unit, Precision::Double);
+            let binary = if velocities {
+                binary.with_velocities()
+            } else {
+                binary
+            };
+            Ok(match kept {
+                Some((particle_count, frames)) => (
+                    Self::Binary(binary.appending(particle_count, frames)),
+                    frames,
+                ),
+                None => (Self::Binary(binary), 0),
+            })
+        }
+    }
+
+    fn write_frame(&mut self, handler: &SimulationHandler) -> io::Result<()> {
+        match self {
+            Self::Xyz(writer) => writer.write_frame(handler),
+            Self::Binary(writer) => writer.write_frame(handler),
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Xyz(writer) => writer.into_inner().map(drop),
+            Self::Binary(writer) => writer.into_inner().map(drop),
+        }
+    }
+}
+
+/// Keep the frames of an extended XYZ trajectory up to `step`, returning the file
+/// positioned after them and their number
+fn keep_xyz_frames(mut file: File, step: u64) -> io::Result<(File, usize)> {
+    let mut reader = BufReader::new(&mut file);
+    let (mut kept_len, mut frames) = (0, 0);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let Ok(particles) = line.trim().parse::<usize>() else {
+            break;
+        };
+        let mut frame_len = line.len();
+        line.clear();
+        frame_len += reader.read_line(&mut line)?;
+        let frame_step = line
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix("Step="))
+            .and_then(|value| value.parse::<u64>().ok());
+        if frame_step.is_none_or(|frame_step| frame_step > step) {
+            break;
+        }
+        for _ in 0..particles {
+            line.clear();
+            frame_len += reader.read_line(&mut line)?;
+        }
+        if !line.ends_with('\n') {
+            // An incomplete last frame, e.g. from an interrupted run
+            break;
+        }
+        kept_len += frame_len as u64;
+        frames += 1;
+    }
+    file.set_len(kept_len)?;
+    file.seek(SeekFrom::End(0))?;
+    Ok((file, frames))
+}
+
+/// Keep the frames of a binary trajectory up to `step`, returning the file positioned
+/// after them and, if the trajectory has a header, its particle count and the number
+/// of frames kept
+fn keep_binary_frames(
+    mut file: File,
+    step: u64,
+    unit: LengthUnit,
+    velocities: bool,
+) -> io::Result<(File, Option<(usize, usize)>)> {
+    if file.metadata()?.len() == 0 {
+        return Ok((file, None));
+    }
+    let mut reader = BinaryTrajectoryReader::new(&mut file)?;
+    let unit_name = format!("{unit:?}");
+    if reader.unit_name() != unit_name
+        || reader.precision() != Precision::Double
+        || reader.has_velocities() != velocities
+    {
+        let velocities = if reader.has_velocities() {
+            "with"
+        } else {
+            "without"
+        };
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "existing trajectory is in {} {velocities} velocities, continue it with the same options",
+                reader.unit_name()
+            ),
+        ));
+    }
+    let frames = reader.frames_through_step(step)?;
+    let kept_len = reader.frame_offset(frames);
+    let particle_count = reader.particle_count();
+    drop(reader);
+
+    let invalid = |_| io::Error::new(io::ErrorKind::InvalidData, "trajectory too large");
+    let kept = (
+        usize::try_from(particle_count).map_err(invalid)?,
+        usize::try_from(frames).map_err(invalid)?,
+    );
+    file.set_len(kept_len)?;
+    file.seek(SeekFrom::End(0))?;
+    Ok((file, Some(kept)))
+}
+
+/// Create the energy log at `path` or, when resuming from `resume_step`, continue an
+/// existing one after its lines up to that step
+fn open_energy_log(path: &Path, resume_step: Option<u64>) -> io::Result<BufWriter<File>> {
+    const HEADER: &str = "step,t [s],kinetic [J],potential [J],total [J]";
+    let Some(step) = resume_step else {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "{HEADER}")?;
+        return Ok(writer);
+    };
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    let mut kept_len = 0;
+    let mut line = String::new();
+    let mut reader = BufReader::new(&mut file);
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+            break;
+        }
+        let line_step = line
+            .split(',')
+            .next()
+            .and_then(|field| field.parse::<u64>().ok());
+        match line_step {
+            Some(line_step) if line_step > step => break,
+            // The header, unless the file is empty
+            None if kept_len > 0 => break,
+            _ => kept_len += line.len() as u64,
+        }
+    }
+    file.set_len(kept_len)?;
+    file.seek(SeekFrom::End(0))?;
+
+    let mut writer = BufWriter::new(file);
+    if kept_len == 0 {
+        writeln!(writer, "{HEADER}")?;
+    }
+    Ok(writer)
+}
+
+fn write_energy_line(writer: &mut impl Write, handler: &SimulationHandler) -> io::Result<()> {
+    let energies = handler.energies();
+    writeln!(
+        writer,
+        "{},{},{},{},{}",
+        energies.step,
+        energies.time.get::<second>(),
+        energies.kinetic.get::<joule>(),
+        energies.potential.get::<joule>(),
+        energies.total().get::<joule>()
+    )
+}
+
+fn save_checkpoint(
+    path: &Path,
+    handler: &SimulationHandler,
+    config: &SimulationConfig,
+) -> io::Result<()> {
+    handler.save_checkpoint(BufWriter::new(File::create(path)?), config)
+}
+
+/// Step the scenario, or the checkpoint if given, writing the requested output
+fn run(scenario_path: &Path, checkpoint: Option<&Path>, args: &RunArgs) -> io::Result<()> {
+    let mut scenario = Scenario::load(scenario_path)?;
+    let (mut handler, config) = match checkpoint {
+        Some(path) => {
+            SimulationHandler::load_checkpoint(BufReader::new(File::open(path)?), |config| {
+                scenario.integrator.build(config)
+            })?
+        }
+        None => {
+            // The seed drawn by `build` is recorded in the checkpoint, and printed, so
+            // the run can be repeated
+            let handler = scenario.build()?;
+            (handler, scenario.config.clone())
+        }
+    };
+    let potential: &PairPotential = &scenario.potential;
+
+    // Stepped one step at a time, so every step can be logged
+    let mut step_config = config.clone();
+    step_config.time_steps_per_frame = None;
+
+    let first_step = handler.step_count();
+    let resume_step = checkpoint.map(|_| first_step);
+    let mut trajectory = match &args.trajectory {
+        Some(path) => {
+            let (mut trajectory, frames) =
+                TrajectoryOutput::open(path, args.length_unit, args.velocities, resume_step)?;
+            // A continued trajectory already holds the frames up to the checkpointed step
+            if frames == 0 {
+                trajectory.write_frame(&handler)?;
+            }
+            Some(trajectory)
+        }
+        None => None,
+    };
+    let mut energy_log = args
+        .energy
+        .as_deref()
+        .map(|path| open_energy_log(path, resume_step))
+        .transpose()?;
+
+    let last_step = first_step + args.steps;
+    eprintln!(
+        "{} particles, steps {first_step} to {last_step}, seed {}",
+        handler.positions().len(),
+        config
+            .seed
+            .map_or_else(|| "none".to_owned(), |seed| seed.to_string())
+    );
+
+    let started = Instant::now();
+    let mut initial_energy = None;
+    while handler.step_count() < last_step {
+        handler.step_physics(&step_config, potential, step_config.time_step)?;
+        let step = handler.step_count();
+        // The potential energy is only known after the first force evaluation
+        initial_energy.get_or_insert(handler.energies().total());
+
+        if let Some(trajectory) = &mut trajectory
+            && step.is_multiple_of(args.trajectory_interval)
+        {
+            trajectory.write_frame(&handler)?;
+        }
+        if let Some(energy_log) = &mut energy_log
+            && step.is_multiple_of(args.energy_interval)
+        {
+            write_energy_line(energy_log, &handler)?;
+        }
+        if let (Some(path), Some(interval)) = (&args.checkpoint, args.checkpoint_interval)
+            && step.is_multiple_of(interval)
+        {
+            save_checkpoint(path, &handler, &config)?;
+        }
+        if step.is_multiple_of(args.progress_interval) {
+            #[allow(clippy::cast_precision_loss)]
+            let rate = (step - first_step) as f64 / started.elapsed().as_secs_f64();
+            eprintln!(
+                "step {step}/{last_step}  t = {:.4e} s  T = {:.2} K  E = {:.6e} J  {rate:.0} steps/s",
+                handler.time().get::<second>(),
+                handler.temperature().get::<kelvin>(),
+                handler.energies().total().get::<joule>(),
+            );
+        }
+    }
+
+    if let Some(trajectory) = trajectory {
+        trajectory.finish()?;
+    }
+    if let Some(mut energy_log) = energy_log {
+        energy_log.flush()?;
+    }
+    if let Some(path) = &args.checkpoint {
+        save_checkpoint(path, &handler, &config)?;
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    let final_energy = handler.energies().total();
+    #[allow(clippy::cast_precision_loss)]
+    let rate = args.steps as f64 / elapsed;
+    println!(
+        "{} steps in {elapsed:.2} s ({rate:.0} steps/s), t = {:.4e} s",
+        args.steps,
+        handler.time().get::<second>()
+    );
+    println!(
+        "final temperature {:.2} K, total energy {:.6e} J",
+        handler.temperature().get::<kelvin>(),
+        final_energy.get::<joule>()
+    );
+    if let Some(initial_energy) = initial_energy {
+        let drift = ((final_energy - initial_energy) / initial_energy.abs()).get::<ratio>();
+        println!("relative energy drift {drift:.3e}");
+    }
+    if let Some(pressure) = handler.pressure() {
+        println!("pressure {:.6e} N/m", pressure.value);
+    }
+    Ok(())
+}
+
+/// Length unit of the given name, as written by the trajectory writers
+fn length_unit_named(name: &str) -> io::Result<LengthUnit> {
+    LengthUnit::deserialize(StrDeserializer::<serde::de::value::Error>::new(name)).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown length unit {name}"),
+        )
+    })
+}
+
+/// Accumulate g(r) and the MSD over the frames of a binary trajectory
+fn analyze(
+    path: &Path,
+    rdf_path: Option<&Path>,
+    bins: usize,
+    max_radius: Option<f64>,
+    msd_path: Option<&Path>,
+    skip: u64,
+) -> io::Result<()> {
+    let mut reader = BinaryTrajectoryReader::new(BufReader::new(File::open(path)?))?;
+    let unit = length_unit_named(reader.unit_name())?;
+    eprintln!(
+        "{} frames of {} particles",
+        reader.len(),
+        reader.particle_count()
+    );
+
+    let mut rdf: Option<RadialDistribution> = None;
+    let mut msd = MeanSquaredDisplacement::new();
+    for index in skip..reader.len() {
+        let frame = reader.read_frame(index)?;
+
+        if let Some(simulation_box) = &frame.simulation_box {
+            let rdf = rdf.get_or_insert_with(|| {
+                let (width_a, width_b) = simulation_box.perpendicular_widths();
+                let max_radius = max_radius.map_or(width_a.min(width_b) / 2., |r| unit.new(r));
+                RadialDistribution::new(max_radius, bins)
+            });
+            rdf.accumulate_positions(&frame.positions, simulation_box);
+        }
+
+        msd.record(frame.time, frame.unwrapped_positions());
+    }
+
+    if let Some(rdf_path) = rdf_path {
+        match &rdf {
+            Some(rdf) => rdf.write_csv(BufWriter::new(File::create(rdf_path)?), unit)?,
+            None => eprintln!("no periodic box, g(r) not computed"),
+        }
+    }
+    if let Some(msd_path) = msd_path {
+        msd.write_csv(BufWriter::new(File::create(msd_path)?), unit)?;
+    }
+
+    // Fit the diffusive regime, past the ballistic start and before statistics get poor
+    let values = msd.values();
+    if let Some(&(max_lag, _)) = values.last() {
+        let fit_start: Time = max_lag / 10.;
+        let fit_end: Time = max_lag / 2.;
+        if let Some(diffusion) = msd.diffusion_coefficient(fit_start, fit_end) {
+            println!("diffusion coefficient {:.6e} m^2/s", diffusion.value);
+        }
+    }
+    Ok(())
+}
@@ -1,88 +1,56 @@
-use molecular_dynamics::{
-    handler::SimulationHandler,
-    physics::{potential::LennardJones, time_integration::StepType},
-    point_mass::PointMass,
-};
-use physics_core::vector::Vector2D;
-use visualization::simulation::{
-    config::SimulationConfigBuilder,
-    units::{LengthUnit, MassUnit},
-};
-
-use uom::si::{
-    acceleration::meter_per_second_squared,
-    energy::electronvolt,
-    f64::{Acceleration, Energy, Length, Mass, Time, Velocity},
-    length::angstrom,
-    mass::dalton,
-    time::femtosecond,
-    velocity::atomic_unit_of_velocity,
-};
+use molecular_dynamics::scenario::{Scenario, load_file};
+use serde::Deserialize;
+use visualization::simulation::config::{DisplayConfig, simulation_setup};
 
 use macroquad::prelude::*;
 
-#[macroquad::main("Argon Gas Large - SimulationHandler")]
-async fn main() {
-    // Simulation config for Argon
-    let config = SimulationConfigBuilder::default()
-        .time_step(Time::new::<femtosecond>(1.0))
-        .length_unit(LengthUnit::Angstrom)
-        .mass_unit(MassUnit::Dalton)
-        .pixels_per_length(0.4)
-        .time_steps_per_frame(Some(10))
-        .build()
-        .unwrap();
-
-    // Initialize argon atoms
-    let max_bound = 1000.0;
-    let mut points: Vec<Box<PointMass>> = Vec::new();
-    for _i in 0..1000 {
-        points.push(Box::new(PointMass::new(
-            Vector2D {
-                x: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-                y: Length::new::<angstrom>(rand::gen_range(-max_bound, max_bound)),
-            },
-            Vector2D {
-                x: Velocity::new::<atomic_unit_of_velocity>(0.0),
-                y: Velocity::new::<atomic_unit_of_velocity>(0.0),
-            },
-            Vector2D {
-                x: Acceleration::new::<meter_per_second_squared>(0.0),
-                y: Acceleration::new::<meter_per_second_squared>(0.0),
-            },
-            Mass::new::<dalton>(39.948),
-            config.time_step,
-        )));
-    }
+/// Scenario run when no path is given on the command line
+const DEFAULT_SCENARIO: &str = "scenarios/argon_large.toml";
 
-    // Create simulation handler
-    let mut handler = SimulationHandler::new(points);
+/// The `[display]` section of a scenario file, which the scenario itself ignores
+#[derive(Deserialize)]
+struct Display {
+    #[serde(default)]
+    display: DisplayConfig,
+}
 
-    // Lennard-Jones potential for Argon
-    let potential = LennardJones {
-        epsilon: Energy::new::<electronvolt>(0.0104),
-        sigma: Length::new::<angstrom>(3.4),
+#[macroquad::main("Physics Engine - Scenario")]
+async fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_SCENARIO.to_owned());
+    let (mut scenario, display) = match Scenario::load(&path).and_then(|scenario| {
+        load_file::<Display>(&path).map(|Display { display }| (scenario, display))
+    }) {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            eprintln!("failed to load scenario {path}: {err}");
+            std::process::exit(1);
+        }
     };
-
-    let color = WHITE;
+    let mut handler = match scenario.build() {
+        Ok(handler) => handler,
+        Err(err) => {
+            eprintln!("invalid scenario {path}: {err}");
+            std::process::exit(1);
+        }
+    };
+    let (config, potential) = (&scenario.config, &scenario.potential);
+    simulation_setup(&display);
 
     loop {
-        // Run multiple physics steps
-        handler.step_physics(
-            &config,
-            &potential,
-            config.time_step,
-            StepType::VelocityVerlet,
-        );
+        // Runs `time_steps_per_frame` steps
+        if let Err(err) = handler.step_physics(config, potential, config.time_step) {
+            eprintln!("simulation of {path} stopped: {err}");
+            std::process::exit(1);
+        }
 
         clear_background(BLACK);
 
         // Sync back to objects for rendering
         handler.sync_to_points();
-
-        // Draw all objects
-        for obj in &handler.points {
-            obj.draw(&config, Some(1.), color);
+        for point in &handler.points {
+            point.draw(&display, Some(2.), WHITE);
         }
 
         next_frame().await;